@@ -0,0 +1,48 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use harness::{Solution, Source};
+use std::hint::black_box;
+
+// Every stage of every day gets its own benchmark, so a slower parser doesn't hide behind a faster part B. We benchmark
+// against the real inputs, the examples are far too small to tell us anything. A day without an input is skipped.
+//
+// Criterion keeps the results of the previous run in target/criterion and reports every change it finds significant.
+// To compare against a known state instead of the last run, save a baseline first:
+//
+//   cargo bench -p aoc -- --save-baseline main
+//   cargo bench -p aoc -- --baseline main
+fn bench_day<S: Solution>(c: &mut Criterion, day: usize) {
+    let Ok(text) = Source::Default.read(day) else {
+        eprintln!("Skipping day {:02}, there is no input", day);
+        return;
+    };
+    let Ok(input) = S::parse(&text) else {
+        eprintln!("Skipping day {:02}, the input doesn't parse", day);
+        return;
+    };
+
+    let mut group = c.benchmark_group(format!("day{:02}", day));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&text))));
+    group.bench_function("part_a", |b| {
+        b.iter(|| S::part_a(black_box(&input)).map(|answer| answer.to_string()))
+    });
+    group.bench_function("part_b", |b| {
+        b.iter(|| S::part_b(black_box(&input)).map(|answer| answer.to_string()))
+    });
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day01::Day01>(c, 1);
+    bench_day::<day02::Day02>(c, 2);
+    bench_day::<day03::Day03>(c, 3);
+    bench_day::<day04::Day04>(c, 4);
+    bench_day::<day05::Day05>(c, 5);
+    bench_day::<day06::Day06>(c, 6);
+    bench_day::<day07::Day07>(c, 7);
+    bench_day::<day08::Day08>(c, 8);
+    bench_day::<day09::Day09>(c, 9);
+    bench_day::<day10::Day10>(c, 10);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
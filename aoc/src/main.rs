@@ -0,0 +1,269 @@
+use harness::{solve_timed, Answers, Check, Recorded, Source, Timings};
+use std::{env, error::Error, ops::RangeInclusive, process::ExitCode};
+
+type Solver = fn(&str) -> Result<(Answers, Timings), Box<dyn Error>>;
+
+// Every day we have solved so far, in calendar order. The index in this list plus one is the day number, so adding a new
+// day is just a matter of adding the dependency and appending its solution here.
+const DAYS: [Solver; 10] = [
+    solve_timed::<day01::Day01>,
+    solve_timed::<day02::Day02>,
+    solve_timed::<day03::Day03>,
+    solve_timed::<day04::Day04>,
+    solve_timed::<day05::Day05>,
+    solve_timed::<day06::Day06>,
+    solve_timed::<day07::Day07>,
+    solve_timed::<day08::Day08>,
+    solve_timed::<day09::Day09>,
+    solve_timed::<day10::Day10>,
+];
+
+const USAGE: &str = "Usage: aoc run <days> [--example <n> | --input <path> | -]
+       aoc verify [<days>]
+       aoc record <days>
+
+Where <days> is one of:
+    6       a single day
+    3..7    days 3 through 7 (inclusive, 3..=7 works too)
+    all     every day we have a solution for
+
+By default the input of a day is read from inputs/dayNN.txt. Instead you can use:
+    --example <n>   the n-th published example, from inputs/examples/dayNN-n.txt
+    --input <path>  any other file (a single day only)
+    -               stdin (a single day only)
+
+Once the answers of a day are accepted, record them in inputs/dayNN.answers.toml with record. After that verify (of all
+days by default) runs the days again and reports every answer that changed.";
+
+enum Command {
+    Run,
+    Verify,
+    Record,
+}
+
+// A single line in the results table. We keep the answers as strings so a failing day can simply show its error in the
+// same table as the days that went fine. Only a plain run shows how long each stage took.
+struct Row {
+    day: usize,
+    part_a: String,
+    part_b: String,
+    timings: Option<Timings>,
+}
+
+impl Row {
+    fn new(day: usize, part_a: impl Into<String>, part_b: impl Into<String>) -> Self {
+        Self {
+            day,
+            part_a: part_a.into(),
+            part_b: part_b.into(),
+            timings: None,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let source = match Source::from_args(&mut args) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (command, days) = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["run", days] => (Command::Run, days.to_string()),
+        ["verify"] => (Command::Verify, "all".to_string()),
+        ["verify", days] => (Command::Verify, days.to_string()),
+        ["record", days] => (Command::Record, days.to_string()),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(days) = parse_days(&days) else {
+        eprintln!("Invalid days: {}\n\n{}", days, USAGE);
+        return ExitCode::FAILURE;
+    };
+
+    // There is only one file and only one stdin, so those can't be the input of more than one day.
+    if matches!(source, Source::Path(_) | Source::Stdin) && days.start() != days.end() {
+        eprintln!("--input and - only work for a single day\n\n{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    // The recorded answers belong to our own inputs, checking them against any other input makes no sense.
+    if !matches!(command, Command::Run) && source != Source::Default {
+        eprintln!(
+            "verify and record only work on the inputs in inputs/\n\n{}",
+            USAGE
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let (rows, success) = match command {
+        Command::Run => run(days, &source),
+        Command::Verify => verify(days),
+        Command::Record => record(days),
+    };
+    print_table(&rows);
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+// Turn "6", "3..7", "3..=7" or "all" into a range of days. Anything outside of the days we have solved is rejected
+// instead of silently skipped, so a typo doesn't look like a day without output.
+fn parse_days(days: &str) -> Option<RangeInclusive<usize>> {
+    let range = if days == "all" {
+        1..=DAYS.len()
+    } else if let Some((start, end)) = days.split_once("..") {
+        let end = end.strip_prefix('=').unwrap_or(end);
+        start.parse().ok()?..=end.parse().ok()?
+    } else {
+        let day = days.parse().ok()?;
+        day..=day
+    };
+
+    if range.is_empty() || *range.start() < 1 || *range.end() > DAYS.len() {
+        return None;
+    }
+
+    Some(range)
+}
+
+fn solve_day(day: usize, source: &Source) -> Result<(Answers, Timings), Box<dyn Error>> {
+    let input = source.read(day)?;
+    DAYS[day - 1](&input)
+}
+
+// A day that fails still gets its row, but the run as a whole fails too.
+fn run(days: RangeInclusive<usize>, source: &Source) -> (Vec<Row>, bool) {
+    let mut success = true;
+
+    let rows = days
+        .map(|day| match solve_day(day, source) {
+            Ok((Answers { part_a, part_b }, timings)) => Row {
+                timings: Some(timings),
+                ..Row::new(day, part_a, part_b)
+            },
+            Err(e) => {
+                success = false;
+                error_row(day, e)
+            }
+        })
+        .collect();
+
+    (rows, success)
+}
+
+fn error_row(day: usize, e: Box<dyn Error>) -> Row {
+    Row::new(day, format!("error: {}", e), "-")
+}
+
+// Run every day again and compare with what we recorded. A day without an input or without recorded answers is not a
+// failure (we can't verify what we don't have), but a day that fails or gives a different answer is.
+fn verify(days: RangeInclusive<usize>) -> (Vec<Row>, bool) {
+    let mut success = true;
+
+    let rows = days
+        .map(|day| {
+            let path = Source::Default.path(day).unwrap();
+            if !path.exists() {
+                return Row::new(day, "no input", "-");
+            }
+
+            let checks = Recorded::load(day)
+                .and_then(|recorded| Ok(recorded.check(&solve_day(day, &Source::Default)?.0)));
+            match checks {
+                Ok((check_a, check_b)) => {
+                    success &= !matches!(check_a, Check::Mismatch { .. });
+                    success &= !matches!(check_b, Check::Mismatch { .. });
+                    Row::new(day, describe(check_a), describe(check_b))
+                }
+                Err(e) => {
+                    success = false;
+                    error_row(day, e)
+                }
+            }
+        })
+        .collect();
+
+    (rows, success)
+}
+
+fn describe(check: Check) -> String {
+    match check {
+        Check::Match => "ok".to_string(),
+        Check::Mismatch { expected, actual } => {
+            format!("MISMATCH: expected {}, got {}", expected, actual)
+        }
+        Check::NotRecorded => "not recorded".to_string(),
+    }
+}
+
+// Record the current answers of the days as the known-good ones. Only do this after the puzzle site accepted them!
+fn record(days: RangeInclusive<usize>) -> (Vec<Row>, bool) {
+    let mut success = true;
+
+    let rows = days
+        .map(|day| {
+            let recorded = solve_day(day, &Source::Default).and_then(|(answers, _)| {
+                Recorded::from(answers.clone()).save(day)?;
+                Ok(answers)
+            });
+            match recorded {
+                Ok(Answers { part_a, part_b }) => Row::new(day, part_a, part_b),
+                Err(e) => {
+                    success = false;
+                    error_row(day, e)
+                }
+            }
+        })
+        .collect();
+
+    (rows, success)
+}
+
+// The timings only get columns when there's at least one day with timings, a verify has nothing to time.
+fn print_table(rows: &[Row]) {
+    let width_a = rows.iter().map(|row| row.part_a.len()).fold(6, usize::max);
+    let width_b = rows.iter().map(|row| row.part_b.len()).fold(6, usize::max);
+    let timed = rows.iter().any(|row| row.timings.is_some());
+
+    if timed {
+        println!(
+            "Day | {:<width_a$} | {:<width_b$} | {:>10} | {:>10} | {:>10}",
+            "Part A", "Part B", "Parse", "Part A", "Part B"
+        );
+        println!(
+            "----+-{}-+-{}-+-{}",
+            "-".repeat(width_a),
+            "-".repeat(width_b),
+            ["----------"; 3].join("-+-")
+        );
+    } else {
+        println!("Day | {:<width_a$} | Part B", "Part A");
+        println!("----+-{}-+-{}", "-".repeat(width_a), "-".repeat(width_b));
+    }
+
+    for row in rows {
+        match (timed, row.timings) {
+            (true, Some(timings)) => println!(
+                "{:>3} | {:<width_a$} | {:<width_b$} | {:>10} | {:>10} | {:>10}",
+                row.day,
+                row.part_a,
+                row.part_b,
+                format!("{:.2?}", timings.parse),
+                format!("{:.2?}", timings.part_a),
+                format!("{:.2?}", timings.part_b),
+            ),
+            _ => println!("{:>3} | {:<width_a$} | {}", row.day, row.part_a, row.part_b),
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use harness::{parse_number, ParseError};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    hash::Hash,
+    str::FromStr,
+};
+
+// The puzzle has two lists of location ids, but reconciling lists doesn't stop at two. This is the general version:
+// any number of columns, any kind of integer as id and a choice of how to compare two columns. The puzzle itself is
+// just two columns of usize compared with the absolute and similarity metrics.
+
+// Anything we can use as an id. The scores are in the same type as the ids, so use a wide enough type when the
+// differences get big (squared differences get big fast). All the arithmetic is checked: a score that doesn't fit is an
+// `Overflow`, not a panic or a number that wrapped around.
+pub trait Id: Copy + Ord + Hash + Debug + Display + FromStr {
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn from_count(count: usize) -> Option<Self>;
+
+    // The distance between two ids, which is never negative. Subtracting the smaller from the larger also works for the
+    // unsigned types, but for the signed ones it can still be too big (i64::MAX - i64::MIN).
+    fn distance(self, other: Self) -> Option<Self> {
+        if self > other {
+            self.checked_sub(other)
+        } else {
+            other.checked_sub(self)
+        }
+    }
+}
+
+macro_rules! impl_id {
+    ($($t:ty),*) => {
+        $(impl Id for $t {
+            const ZERO: Self = 0;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn from_count(count: usize) -> Option<Self> {
+                <$t>::try_from(count).ok()
+            }
+        })*
+    };
+}
+
+impl_id!(i32, i64, i128, u32, u64, u128, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    // The sum of the distances between the sorted columns, part A.
+    Absolute,
+    // The same, but every distance squared, so a few big differences weigh more than a lot of small ones.
+    Squared,
+    // Every id in the left column times how often it appears in the right column, part B. Unlike the others this one
+    // isn't symmetric.
+    Similarity,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [Metric::Absolute, Metric::Squared, Metric::Similarity];
+
+    // Both columns have to be sorted.
+    pub fn score<T: Id>(self, left: &[T], right: &[T]) -> Result<T, Overflow> {
+        let pairs = left.iter().zip(right);
+        let score = match self {
+            Metric::Absolute => pairs.map(|(&a, &b)| a.distance(b)).try_fold(T::ZERO, add),
+            Metric::Squared => pairs
+                .map(|(&a, &b)| a.distance(b).and_then(|d| d.checked_mul(d)))
+                .try_fold(T::ZERO, add),
+            Metric::Similarity => {
+                let mut counts: HashMap<T, usize> = HashMap::new();
+                for &b in right {
+                    *counts.entry(b).or_default() += 1;
+                }
+
+                left.iter()
+                    .map(|&a| a.checked_mul(T::from_count(counts.get(&a).copied().unwrap_or(0))?))
+                    .try_fold(T::ZERO, add)
+            }
+        };
+        score.ok_or(Overflow { metric: self })
+    }
+}
+
+// Add a term to a sum, where either can already have overflowed.
+fn add<T: Id>(sum: T, term: Option<T>) -> Option<T> {
+    sum.checked_add(term?)
+}
+
+// A score that doesn't fit in the id type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub metric: Metric,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "the {:?} score doesn't fit in the id type, pick a wider one (like u128 or i128)",
+            self.metric
+        )
+    }
+}
+
+impl Error for Overflow {}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "absolute" => Ok(Metric::Absolute),
+            "squared" => Ok(Metric::Squared),
+            "similarity" => Ok(Metric::Similarity),
+            _ => Err(format!(
+                "unknown metric {}, expected absolute, squared or similarity",
+                name
+            )),
+        }
+    }
+}
+
+// The score of a metric for a single pair of columns. Columns are numbered from 0, left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PairReport<T> {
+    pub left: usize,
+    pub right: usize,
+    pub score: T,
+}
+
+// All columns of the input, every one of them sorted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Columns<T> {
+    columns: Vec<Vec<T>>,
+}
+
+impl<T: Id> Columns<T> {
+    // The number of columns is taken from the first line unless we ask for a specific number. Either way, every line
+    // needs to have exactly that many ids.
+    pub fn parse(input: &str, width: Option<usize>) -> Result<Self, ParseError> {
+        let mut columns: Vec<Vec<T>> = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let width = width.unwrap_or(if index == 0 {
+                line.split_whitespace().count()
+            } else {
+                columns.len()
+            });
+            let row = parse_row(line, width).map_err(|e| e.on_line(index + 1))?;
+
+            columns.resize_with(width, Vec::new);
+            for (column, id) in columns.iter_mut().zip(row) {
+                column.push(id);
+            }
+        }
+
+        if let Some(width) = width {
+            columns.resize_with(width, Vec::new);
+        }
+        for column in columns.iter_mut() {
+            column.sort();
+        }
+
+        Ok(Self { columns })
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    pub fn column(&self, index: usize) -> &[T] {
+        &self.columns[index]
+    }
+
+    pub fn into_columns(self) -> Vec<Vec<T>> {
+        self.columns
+    }
+
+    // Compare every column with every other column. Similarity isn't symmetric, so there we do both directions.
+    pub fn report(&self, metric: Metric) -> Result<Vec<PairReport<T>>, Overflow> {
+        let n = self.columns.len();
+        let pairs = (0..n).flat_map(|left| (0..n).map(move |right| (left, right)));
+
+        pairs
+            .filter(|&(left, right)| match metric {
+                Metric::Similarity => left != right,
+                _ => left < right,
+            })
+            .map(|(left, right)| {
+                Ok(PairReport {
+                    left,
+                    right,
+                    score: metric.score(&self.columns[left], &self.columns[right])?,
+                })
+            })
+            .collect()
+    }
+}
+
+// Exactly `width` ids, separated by whitespace.
+pub fn parse_row<T: FromStr>(line: &str, width: usize) -> Result<Vec<T>, ParseError> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let reason = || match width {
+        1 => "expected a single number".to_string(),
+        2 => "expected two numbers".to_string(),
+        n => format!("expected {} numbers", n),
+    };
+
+    if parts.len() < width {
+        return Err(ParseError::at_end(line, reason()));
+    }
+    if parts.len() > width {
+        return Err(ParseError::in_line(line, parts[width], reason()));
+    }
+
+    parts.iter().map(|part| parse_number(line, part)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THREE_COLUMNS: &str = "3 4 1\n4 3 1\n2 5 4\n";
+
+    #[test]
+    fn reports_every_pair_of_columns() {
+        let columns: Columns<i64> = Columns::parse(THREE_COLUMNS, None).unwrap();
+        assert_eq!(columns.len(), 3);
+
+        let scores = |metric| {
+            columns
+                .report(metric)
+                .unwrap()
+                .iter()
+                .map(|report| (report.left, report.right, report.score))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            scores(Metric::Absolute),
+            vec![(0, 1, 3), (0, 2, 3), (1, 2, 6)]
+        );
+        assert_eq!(
+            scores(Metric::Squared),
+            vec![(0, 1, 3), (0, 2, 5), (1, 2, 14)]
+        );
+        assert_eq!(
+            scores(Metric::Similarity),
+            vec![
+                (0, 1, 7),
+                (0, 2, 4),
+                (1, 0, 7),
+                (1, 2, 4),
+                (2, 0, 4),
+                (2, 1, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn signed_and_wide_ids() {
+        let columns: Columns<i128> =
+            Columns::parse("-5 5\n-170141183460469231731687303715884105728 0\n", None).unwrap();
+        assert_eq!(columns.column(0)[0], i128::MIN);
+        assert_eq!(Metric::Absolute.score(&[-5i64], &[5]), Ok(10));
+
+        let big = u128::MAX / 2;
+        assert_eq!(Metric::Absolute.score(&[big], &[big + 3]), Ok(3));
+    }
+
+    #[test]
+    fn scores_that_dont_fit() {
+        let columns: Columns<u64> = Columns::parse("4294967296 0\n", None).unwrap();
+        assert_eq!(columns.report(Metric::Absolute).unwrap()[0].score, 1 << 32);
+
+        let error = columns.report(Metric::Squared).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the Squared score doesn't fit in the id type, pick a wider one (like u128 or i128)"
+        );
+        assert_eq!(
+            Metric::Absolute.score(&[i64::MIN], &[i64::MAX]),
+            Err(Overflow {
+                metric: Metric::Absolute
+            })
+        );
+        assert!(Metric::Similarity
+            .score(&[u64::MAX], &[u64::MAX, u64::MAX])
+            .is_err());
+    }
+
+    #[test]
+    fn every_line_needs_the_same_width() {
+        let error = Columns::<u64>::parse("1 2 3\n4 5\n", None).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 4: expected 3 numbers");
+
+        let error = Columns::<u64>::parse("1 2 3\n", Some(2)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: expected two numbers (found \"3\")"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use harness::{ParseError, Solution};
+use std::{error::Error, fmt::Display};
+
+pub mod columns;
+pub mod stream;
+
+pub use columns::{Columns, Id, Metric, Overflow, PairReport};
+
+// Both lists of location ids, sorted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationLists {
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+}
+
+// The puzzle is the two column case of the general columns.
+pub fn parse(input: &str) -> Result<LocationLists, ParseError> {
+    let mut columns = Columns::parse(input, Some(2))?.into_columns().into_iter();
+    let left = columns.next().unwrap_or_default();
+    let right = columns.next().unwrap_or_default();
+
+    Ok(LocationLists { left, right })
+}
+
+// Lists are sorted here, so we just take the difference between pairwise elements and sum them up.
+pub fn part_a(lists: &LocationLists) -> Result<usize, Overflow> {
+    Metric::Absolute.score(&lists.left, &lists.right)
+}
+
+// This used to scan the whole right list for every number on the left, which is fine for a thousand lines but not for a
+// billion. Counting the right list once makes it a single lookup per number on the left.
+pub fn part_b(lists: &LocationLists) -> Result<usize, Overflow> {
+    Metric::Similarity.score(&lists.left, &lists.right)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = LocationLists;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(lists: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(lists)?)
+    }
+
+    fn part_b(lists: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_b(lists)?)
+    }
+}
+
+// Extract the two (unsigned) numbers from the line.
+pub(crate) fn parse_line(line: &str) -> Result<(usize, usize), ParseError> {
+    let row = columns::parse_row(line, 2)?;
+    Ok((row[0], row[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day01-1.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day01>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_a, "11");
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day01>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_b, "31");
+    }
+}
@@ -1,59 +1,61 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-
-fn main() -> io::Result<()> {
-    let reader = io::BufReader::new(File::open("input")?);
-
-    let (mut left, mut right): (Vec<usize>, Vec<usize>) = reader
-        .lines()
-        .filter_map(|line| match line {
-            Ok(line) => parse_line(&line).or_else(|| {
-                eprintln!("Failed to parse line: {}", line);
-                None
-            }),
-            // Reading a line can also err, if we suddenly lose access to the file or something.
-            Err(e) => {
-                eprintln!("Failed to read line: {}", e);
-                None
-            }
-        })
-        .unzip();
-
-    left.sort();
-    right.sort();
-
-    let part_a_result = part_a(&left, &right);
-    println!("Part A: {}", part_a_result);
-
-    let part_b_result = part_b(&left, &right);
-    println!("Part B: {}", part_b_result);
-
-    Ok(())
+use day01::{stream, Columns, Day01, Id, Metric};
+use harness::Source;
+use std::{env, error::Error, process::ExitCode};
+
+// Besides the puzzle itself there are two other modes:
+//
+//   cargo run -- stream                   reads the input line by line instead of all at once. That's slower for the
+//                                         puzzle input, but it works for inputs that don't fit in memory.
+//   cargo run -- report <metric> [type]   compares every pair of columns of an input with any number of columns. The
+//                                         metric is absolute, squared, similarity or all, the type of the ids is u64
+//                                         (the default), i64, u128 or i128.
+fn main() -> ExitCode {
+    harness::exit(run())
 }
 
-// Lists are sorted here, so we just take the difference between pairwise elements and sum them up.
-fn part_a(left: &Vec<usize>, right: &Vec<usize>) -> usize {
-    left.iter()
-        .zip(right.iter())
-        .map(|(&a, &b)| usize::max(a, b) - usize::min(a, b))
-        .sum()
+fn run() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let source = Source::from_args(&mut args)?;
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["stream"] => {
+            let (distance, similarity) = stream::solve(source.open(1)?, stream::RUN_SIZE)?;
+
+            println!("Part A: {}", distance);
+            println!("Part B: {}", similarity);
+            Ok(())
+        }
+        ["report", metric] => report::<u64>(&source, metric),
+        ["report", metric, "u64"] => report::<u64>(&source, metric),
+        ["report", metric, "i64"] => report::<i64>(&source, metric),
+        ["report", metric, "u128"] => report::<u128>(&source, metric),
+        ["report", metric, "i128"] => report::<i128>(&source, metric),
+        ["report", ..] => {
+            Err("usage: report <absolute|squared|similarity|all> [u64|i64|u128|i128]".into())
+        }
+        _ => harness::run::<Day01>(1, &source),
+    }
 }
 
-// Not the fastest solution, but it works on what we already have. If there would have been a billion entires we should
-// have used a lookup structure for the right side and precount the values.
-fn part_b(left: &Vec<usize>, right: &Vec<usize>) -> usize {
-    left.iter()
-        .map(|a| {
-            let count = right.iter().filter(|&b| a == b).count();
-            a * count
-        })
-        .sum()
-}
+fn report<T: Id>(source: &Source, metric: &str) -> Result<(), Box<dyn Error>> {
+    let metrics = match metric {
+        "all" => Metric::ALL.to_vec(),
+        metric => vec![metric.parse()?],
+    };
+    let columns: Columns<T> = Columns::parse(&source.read(1)?, None)?;
+
+    // Columns are numbered from 1 here, just like you'd count them in the file.
+    for metric in metrics {
+        println!("{:?}", metric);
+        for report in columns.report(metric)? {
+            println!(
+                "  {} - {}: {}",
+                report.left + 1,
+                report.right + 1,
+                report.score
+            );
+        }
+    }
 
-// Extract the two (unsigned) numbers from the line.
-fn parse_line(line: &str) -> Option<(usize, usize)> {
-    let mut parts = line.split_whitespace();
-    let a = parts.next()?.parse().ok()?;
-    let b = parts.next()?.parse().ok()?;
-    Some((a, b))
+    Ok(())
 }
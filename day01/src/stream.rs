@@ -0,0 +1,276 @@
+use crate::parse_line;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    env,
+    error::Error,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+    process, slice,
+    sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+};
+
+// The regular solution keeps both lists in memory, which is perfect for the puzzle input. This is the version for
+// inputs that are a little bigger than that: the lines are read one by one and each column goes into an external sort.
+// That keeps at most `run_size` numbers per column in memory, every time the buffer is full it is sorted and written to
+// a temporary file (a run). Reading the sorted columns back is a k-way merge over all runs.
+//
+// Once both columns are sorted the answers are easy. The distance pairs up the two sorted columns, just like part A.
+// For the similarity we walk both sorted columns side by side in runs of equal numbers, so we know how often a number
+// is on the left and on the right without looking anything up. That's O(n log n) for the sorting and a linear pass for
+// each answer.
+
+// Four million numbers per column per run, that's 64MB for both columns together.
+pub const RUN_SIZE: usize = 1 << 22;
+
+pub fn solve<R: BufRead>(reader: R, run_size: usize) -> Result<(usize, usize), Box<dyn Error>> {
+    let mut left = ExternalSort::new(run_size);
+    let mut right = ExternalSort::new(run_size);
+
+    for (index, line) in reader.lines().enumerate() {
+        let (a, b) = parse_line(&line?).map_err(|e| e.on_line(index + 1))?;
+        left.push(a)?;
+        right.push(b)?;
+    }
+    left.finish();
+    right.finish();
+
+    Ok((distance(&left, &right)?, similarity(&left, &right)?))
+}
+
+fn distance(left: &ExternalSort, right: &ExternalSort) -> io::Result<usize> {
+    let mut distance = 0;
+    for (a, b) in left.sorted()?.zip(right.sorted()?) {
+        distance += a?.abs_diff(b?);
+    }
+    Ok(distance)
+}
+
+fn similarity(left: &ExternalSort, right: &ExternalSort) -> io::Result<usize> {
+    let mut left = RunLength::new(left.sorted()?);
+    let mut right = RunLength::new(right.sorted()?);
+
+    let mut similarity = 0;
+    let (mut a, mut b) = (left.next()?, right.next()?);
+    while let (Some((value_a, count_a)), Some((value_b, count_b))) = (a, b) {
+        match value_a.cmp(&value_b) {
+            Ordering::Less => a = left.next()?,
+            Ordering::Greater => b = right.next()?,
+            Ordering::Equal => {
+                similarity += value_a * count_a * count_b;
+                a = left.next()?;
+                b = right.next()?;
+            }
+        }
+    }
+    Ok(similarity)
+}
+
+// Numbers are pushed in any order and read back sorted. Whatever is left in the buffer at the end stays in memory, so
+// an input that fits in a single run never touches the disk at all.
+struct ExternalSort {
+    run_size: usize,
+    buffer: Vec<usize>,
+    runs: Vec<Run>,
+}
+
+impl ExternalSort {
+    fn new(run_size: usize) -> Self {
+        Self {
+            run_size: run_size.max(1),
+            buffer: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, value: usize) -> io::Result<()> {
+        self.buffer.push(value);
+        if self.buffer.len() >= self.run_size {
+            self.buffer.sort_unstable();
+            self.runs.push(Run::write(&self.buffer)?);
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+
+    // Sort the numbers that didn't make it into a run. Only after this the sort can be read.
+    fn finish(&mut self) {
+        self.buffer.sort_unstable();
+    }
+
+    // Every call starts a new merge from the beginning of all runs, so we can read the sorted numbers more than once.
+    fn sorted(&self) -> io::Result<Merge<'_>> {
+        let mut sources = self
+            .runs
+            .iter()
+            .map(|run| run.open().map(Source::File))
+            .collect::<io::Result<Vec<_>>>()?;
+        sources.push(Source::Memory(self.buffer.iter()));
+        Merge::new(sources)
+    }
+}
+
+// A sorted run on disk, as raw 8 byte little endian numbers. The file is removed when the run is dropped.
+struct Run {
+    path: PathBuf,
+}
+
+impl Run {
+    fn write(values: &[usize]) -> io::Result<Self> {
+        // Every run needs its own file, also when more than one sort is going on (like in the tests).
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let id = NEXT.fetch_add(1, AtomicOrdering::Relaxed);
+        let path = env::temp_dir().join(format!("day01-{}-{}.run", process::id(), id));
+
+        let run = Self { path };
+        let mut writer = BufWriter::new(File::create(&run.path)?);
+        for &value in values {
+            writer.write_all(&(value as u64).to_le_bytes())?;
+        }
+        writer.flush()?;
+
+        Ok(run)
+    }
+
+    fn open(&self) -> io::Result<BufReader<File>> {
+        File::open(&self.path).map(BufReader::new)
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+enum Source<'a> {
+    File(BufReader<File>),
+    Memory(slice::Iter<'a, usize>),
+}
+
+impl Source<'_> {
+    fn next(&mut self) -> io::Result<Option<usize>> {
+        match self {
+            Source::Memory(values) => Ok(values.next().copied()),
+            Source::File(reader) => {
+                let mut bytes = [0; 8];
+                match reader.read_exact(&mut bytes) {
+                    Ok(()) => Ok(Some(u64::from_le_bytes(bytes) as usize)),
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+}
+
+// The k-way merge. The heap holds the next number of every source that isn't empty yet, together with the index of the
+// source, so the smallest one tells us which source to read the next number from.
+struct Merge<'a> {
+    sources: Vec<Source<'a>>,
+    heap: BinaryHeap<Reverse<(usize, usize)>>,
+}
+
+impl<'a> Merge<'a> {
+    fn new(mut sources: Vec<Source<'a>>) -> io::Result<Self> {
+        let mut heap = BinaryHeap::with_capacity(sources.len());
+        for (index, source) in sources.iter_mut().enumerate() {
+            if let Some(value) = source.next()? {
+                heap.push(Reverse((value, index)));
+            }
+        }
+        Ok(Self { sources, heap })
+    }
+}
+
+impl Iterator for Merge<'_> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((value, index)) = self.heap.pop()?;
+        match self.sources[index].next() {
+            Ok(Some(next)) => self.heap.push(Reverse((next, index))),
+            Ok(None) => {}
+            Err(e) => return Some(Err(e)),
+        }
+        Some(Ok(value))
+    }
+}
+
+// Turns a sorted stream of numbers into (number, how often it occurs) pairs.
+struct RunLength<'a> {
+    merge: Merge<'a>,
+    pending: Option<usize>,
+}
+
+impl<'a> RunLength<'a> {
+    fn new(merge: Merge<'a>) -> Self {
+        Self {
+            merge,
+            pending: None,
+        }
+    }
+
+    fn next(&mut self) -> io::Result<Option<(usize, usize)>> {
+        let value = match self.pending.take() {
+            Some(value) => value,
+            None => match self.merge.next().transpose()? {
+                Some(value) => value,
+                None => return Ok(None),
+            },
+        };
+
+        let mut count = 1;
+        while let Some(next) = self.merge.next().transpose()? {
+            if next != value {
+                self.pending = Some(next);
+                break;
+            }
+            count += 1;
+        }
+        Ok(Some((value, count)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day01-1.txt");
+
+    #[test]
+    fn example_with_tiny_runs() {
+        // Runs of two numbers, so most of the example ends up on disk.
+        assert_eq!(solve(EXAMPLE.as_bytes(), 2).unwrap(), (11, 31));
+        assert_eq!(solve(EXAMPLE.as_bytes(), RUN_SIZE).unwrap(), (11, 31));
+    }
+
+    #[test]
+    fn same_answers_as_in_memory() {
+        // A simple linear congruential generator, with lots of duplicates because of the modulo.
+        let mut seed: usize = 2024;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % 500
+        };
+        let input: String = (0..5000)
+            .map(|_| format!("{}   {}\n", next(), next()))
+            .collect();
+
+        let lists = crate::parse(&input).unwrap();
+        let expected = (
+            crate::part_a(&lists).unwrap(),
+            crate::part_b(&lists).unwrap(),
+        );
+        assert_eq!(solve(input.as_bytes(), 333).unwrap(), expected);
+    }
+
+    #[test]
+    fn errors_know_their_line() {
+        let error = solve("3   4\n4   x\n".as_bytes(), 1).unwrap_err();
+        assert!(error.to_string().starts_with("line 2, column 5"));
+    }
+}
@@ -0,0 +1,490 @@
+use harness::{parse_number, ParseError, Solution};
+use itertools::Itertools;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+pub mod policy;
+
+pub use policy::{Fault, Monotonicity, SafetyPolicy};
+
+// The first step of a level that breaks the policy: the window is the index of the first of the two readings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub window: usize,
+    pub fault: Fault,
+}
+
+// Why a level is (un)safe. A level that is safe as it is has nothing to explain, otherwise we get the first step that
+// is wrong and, if there is one, the readings to drop to make it safe anyway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    Repaired {
+        violation: Violation,
+        dropped: Vec<usize>,
+    },
+    Unsafe {
+        violation: Violation,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Level {
+    values: Vec<usize>,
+}
+
+impl Level {
+    pub fn values(&self) -> &[usize] {
+        &self.values
+    }
+
+    pub fn is_safe(&self) -> bool {
+        self.is_safe_under(&SafetyPolicy::STRICT)
+    }
+
+    pub fn has_safe_sublevel(&self) -> bool {
+        self.is_safe_under(&SafetyPolicy::DAMPENED)
+    }
+
+    pub fn explain(&self, policy: &SafetyPolicy) -> Verdict {
+        let Some(violation) = self.first_violation(policy) else {
+            return Verdict::Safe;
+        };
+
+        match self.evaluate(policy) {
+            Some(dropped) => Verdict::Repaired { violation, dropped },
+            None => Verdict::Unsafe { violation },
+        }
+    }
+
+    fn first_violation(&self, policy: &SafetyPolicy) -> Option<Violation> {
+        let mut direction = policy.direction;
+        for (window, w) in self.values.windows(2).enumerate() {
+            match policy.check_step(w[0], w[1], direction) {
+                Ok(next) => direction = next,
+                Err(fault) => return Some(Violation { window, fault }),
+            }
+        }
+        None
+    }
+
+    // Check the level against any policy. When it's safe we get the indices of the readings we had to drop to make it
+    // safe (so nothing for a level that is safe as it is), as few as possible. None means that even dropping
+    // `tolerance` readings doesn't help.
+    pub fn evaluate(&self, policy: &SafetyPolicy) -> Option<Vec<usize>> {
+        if self.values.is_empty() {
+            return Some(vec![]);
+        }
+
+        // For the dropped indices we need to walk back through the costs of every reading, so this time we keep all of
+        // them instead of just the window.
+        let mut costs = vec![[UNREACHABLE; 3]; self.values.len()];
+        let dropped = fewest_dropped(&self.values, policy, &mut costs)?;
+        Some(self.dropped_indices(policy, &costs, dropped))
+    }
+
+    // The same question without the answer to which readings to drop, and without allocating anything (unless the
+    // tolerance is silly large).
+    pub fn is_safe_under(&self, policy: &SafetyPolicy) -> bool {
+        let window = policy.tolerance + 2;
+        if window <= STACK_WINDOW {
+            let mut costs = [[UNREACHABLE; 3]; STACK_WINDOW];
+            fewest_dropped(&self.values, policy, &mut costs[..window]).is_some()
+        } else {
+            fewest_dropped(&self.values, policy, &mut vec![[UNREACHABLE; 3]; window]).is_some()
+        }
+    }
+
+    // Walk back from the last reading we keep to the first, every time looking for a reading before it that explains
+    // its cost. We try the closest reading first, so we keep late readings and drop early ones when there's a choice.
+    fn dropped_indices(
+        &self,
+        policy: &SafetyPolicy,
+        costs: &[[usize; 3]],
+        dropped: usize,
+    ) -> Vec<usize> {
+        let (n, k) = (self.values.len(), policy.tolerance);
+        let start = state(policy.direction);
+
+        let (mut i, mut s) = (n.saturating_sub(k + 1)..n)
+            .flat_map(|i| (0..3).map(move |s| (i, s)))
+            .find(|&(i, s)| costs[i][s] != UNREACHABLE && costs[i][s] + (n - 1 - i) == dropped)
+            .expect("there is a last reading that explains the cost");
+
+        let mut kept = vec![i];
+        while !(costs[i][s] == i && s == start) {
+            (i, s) = (i.saturating_sub(k + 1)..i)
+                .rev()
+                .flat_map(|j| (0..3).map(move |t| (j, t)))
+                .find(|&(j, t)| {
+                    costs[j][t] != UNREACHABLE
+                        && costs[j][t] + (i - j - 1) == costs[i][s]
+                        && policy.step(self.values[j], self.values[i], STATES[t]) == Some(STATES[s])
+                })
+                .expect("there is a reading before it that explains the cost");
+            kept.push(i);
+        }
+
+        (0..n).filter(|i| !kept.contains(i)).collect()
+    }
+
+    // The original way of dampening: simply try every combination of readings to drop, smallest first. That's way too
+    // slow for anything but the puzzle, but it's so simple it has to be right. So we keep it around to check the fast
+    // version against.
+    pub fn evaluate_brute_force(&self, policy: &SafetyPolicy) -> Option<Vec<usize>> {
+        (0..=policy.tolerance.min(self.values.len()))
+            .flat_map(|n| self.generate_sublevels(n))
+            .find(|(_, sublevel)| policy.allows(&sublevel.values))
+            .map(|(dropped, _)| dropped)
+    }
+
+    // Day 2 and we're getting to them lifetimes. Because we're iterating over self.values, we have to make sure that
+    // the returned stuff doesn't outlive the original Level or the iterator would break on the next iteration. The
+    // annotation says as much as: the iterator (and all its iterations) will live as long as the Level input (self).
+    //
+    // Every sublevel has n readings less, we try every combination of n indices to drop.
+    fn generate_sublevels<'a>(
+        &'a self,
+        n: usize,
+    ) -> impl Iterator<Item = (Vec<usize>, Level)> + 'a {
+        (0..self.values.len()).combinations(n).map(move |dropped| {
+            let values = self
+                .values
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !dropped.contains(i))
+                .map(|(_, &value)| value)
+                .collect();
+            (dropped, Level { values })
+        })
+    }
+}
+
+// The fast dampener. Cloning the level for every combination of readings to drop is O(n^k) levels to check, but we
+// can do it in a single pass instead. For every reading we keep track of the fewest readings we need to drop before it
+// to get a safe level that ends at that reading, one cost for every direction the level can be going at that point
+// (up, down or still undecided).
+//
+// A reading can follow any of the k + 1 readings before it, dropping everything in between, so we only ever need the
+// costs of the last k + 2 readings. Those live in a ring buffer. The level is safe if we can end at one of the last
+// k + 1 readings, dropping the ones after it, with at most k readings dropped in total.
+const UNREACHABLE: usize = usize::MAX;
+const STATES: [Monotonicity; 3] = [
+    Monotonicity::Increasing,
+    Monotonicity::Decreasing,
+    Monotonicity::Either,
+];
+// Enough for a tolerance of 6, which should be plenty. Anything bigger gets its window on the heap.
+const STACK_WINDOW: usize = 8;
+
+fn state(direction: Monotonicity) -> usize {
+    STATES.iter().position(|&s| s == direction).unwrap()
+}
+
+// The costs of reading i are in costs[i % costs.len()], so the buffer needs room for at least k + 2 readings (or for
+// all of them, then nothing is ever overwritten).
+fn fewest_dropped(
+    values: &[usize],
+    policy: &SafetyPolicy,
+    costs: &mut [[usize; 3]],
+) -> Option<usize> {
+    let (n, k, window) = (values.len(), policy.tolerance, costs.len());
+    if n == 0 {
+        return Some(0);
+    }
+
+    let mut fewest = UNREACHABLE;
+    for i in 0..n {
+        // Starting the level here means dropping everything before it.
+        let mut cost = [UNREACHABLE; 3];
+        if i <= k {
+            cost[state(policy.direction)] = i;
+        }
+
+        for j in i.saturating_sub(k + 1)..i {
+            for (s, &previous) in costs[j % window].iter().enumerate() {
+                let total = previous.saturating_add(i - j - 1);
+                if total > k {
+                    continue;
+                }
+                if let Some(next) = policy.step(values[j], values[i], STATES[s]) {
+                    let slot = &mut cost[state(next)];
+                    *slot = (*slot).min(total);
+                }
+            }
+        }
+
+        costs[i % window] = cost;
+        if n - 1 - i <= k {
+            let end = cost.iter().min().unwrap().saturating_add(n - 1 - i);
+            fewest = fewest.min(end);
+        }
+    }
+
+    (fewest <= k).then_some(fewest)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Level>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| line.parse().map_err(|e: ParseError| e.on_line(index + 1)))
+        .collect()
+}
+
+pub fn part_a(levels: &[Level]) -> usize {
+    count_safe(levels, &SafetyPolicy::STRICT)
+}
+
+pub fn part_b(levels: &[Level]) -> usize {
+    count_safe(levels, &SafetyPolicy::DAMPENED)
+}
+
+pub fn count_safe(levels: &[Level], policy: &SafetyPolicy) -> usize {
+    levels
+        .iter()
+        .filter(|level| level.is_safe_under(policy))
+        .count()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Level>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(levels: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(levels))
+    }
+
+    fn part_b(levels: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_b(levels))
+    }
+}
+
+// Readings are numbered from 1 here, that's how you'd count them in the input.
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let violation = |f: &mut Formatter, v: &Violation| {
+            write!(
+                f,
+                "{} between readings {} and {}",
+                v.fault,
+                v.window + 1,
+                v.window + 2
+            )
+        };
+
+        match self {
+            Verdict::Safe => write!(f, "safe"),
+            Verdict::Repaired {
+                violation: v,
+                dropped,
+            } => {
+                write!(f, "safe after dropping reading ")?;
+                let dropped: Vec<String> = dropped.iter().map(|i| (i + 1).to_string()).collect();
+                write!(f, "{} (", dropped.join(", "))?;
+                violation(f, v)?;
+                write!(f, ")")
+            }
+            Verdict::Unsafe { violation: v } => {
+                write!(f, "unsafe: ")?;
+                violation(f, v)
+            }
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let values = line
+            .split_whitespace()
+            .map(|part| parse_number(line, part))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Level { values })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day02-1.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day02>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_a, "2");
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day02>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_b, "4");
+    }
+
+    #[test]
+    fn evaluate_reports_the_dropped_readings() {
+        let levels = parse(EXAMPLE).unwrap();
+        let dropped = |policy: &SafetyPolicy| {
+            levels
+                .iter()
+                .map(|level| level.evaluate(policy))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            dropped(&SafetyPolicy::DAMPENED),
+            vec![
+                Some(vec![]),
+                None,
+                None,
+                Some(vec![1]),
+                Some(vec![2]),
+                Some(vec![])
+            ]
+        );
+
+        // 1 3 6 7 9 is increasing, so it isn't safe when we want the readings to go down.
+        let decreasing = SafetyPolicy {
+            direction: Monotonicity::Decreasing,
+            ..SafetyPolicy::STRICT
+        };
+        assert_eq!(dropped(&decreasing)[5], None);
+        assert_eq!(dropped(&decreasing)[0], Some(vec![]));
+    }
+
+    #[test]
+    fn the_dampener_drops_early_readings() {
+        let level: Level = "1 3 2 4 5".parse().unwrap();
+        assert_eq!(level.evaluate(&SafetyPolicy::DAMPENED), Some(vec![1]));
+
+        let level: Level = "9 1 2 3 4 9".parse().unwrap();
+        assert_eq!(
+            level.evaluate(&SafetyPolicy::STRICT.with_tolerance(2)),
+            Some(vec![0, 5])
+        );
+        assert!(level.is_safe_under(&SafetyPolicy::STRICT.with_tolerance(20)));
+        assert!(!level.is_safe_under(&SafetyPolicy::DAMPENED));
+    }
+
+    #[test]
+    fn explain_the_example() {
+        let verdicts: Vec<Verdict> = parse(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|level| level.explain(&SafetyPolicy::DAMPENED))
+            .collect();
+
+        let violation = |window, fault| Violation { window, fault };
+        assert_eq!(
+            verdicts,
+            vec![
+                Verdict::Safe,
+                Verdict::Unsafe {
+                    violation: violation(1, Fault::StepTooLarge)
+                },
+                Verdict::Unsafe {
+                    violation: violation(2, Fault::StepTooLarge)
+                },
+                Verdict::Repaired {
+                    violation: violation(1, Fault::DirectionFlip),
+                    dropped: vec![1]
+                },
+                Verdict::Repaired {
+                    violation: violation(2, Fault::ZeroStep),
+                    dropped: vec![2]
+                },
+                Verdict::Safe,
+            ]
+        );
+        assert_eq!(
+            verdicts[3].to_string(),
+            "safe after dropping reading 2 (direction flip between readings 2 and 3)"
+        );
+        assert_eq!(
+            verdicts[1].to_string(),
+            "unsafe: step too large between readings 2 and 3"
+        );
+    }
+
+    #[test]
+    fn policies_can_be_tweaked() {
+        let level: Level = "1 1 2 9 10".parse().unwrap();
+
+        assert_eq!(level.evaluate(&SafetyPolicy::DAMPENED), None);
+        assert_eq!(
+            level.evaluate(&SafetyPolicy::STRICT.with_tolerance(2)),
+            None
+        );
+
+        let wide_steps = SafetyPolicy {
+            max_step: 7,
+            allow_equal: true,
+            ..SafetyPolicy::STRICT
+        };
+        assert_eq!(level.evaluate(&wide_steps), Some(vec![]));
+
+        let no_equal = SafetyPolicy {
+            max_step: 7,
+            tolerance: 2,
+            ..SafetyPolicy::STRICT
+        };
+        assert_eq!(level.evaluate(&no_equal), Some(vec![0]));
+    }
+
+    fn any_policy() -> impl Strategy<Value = SafetyPolicy> {
+        let direction = prop_oneof![
+            Just(Monotonicity::Increasing),
+            Just(Monotonicity::Decreasing),
+            Just(Monotonicity::Either),
+        ];
+        (0..3usize, 0..4usize, any::<bool>(), direction, 0..4usize).prop_map(
+            |(min_step, extra, allow_equal, direction, tolerance)| SafetyPolicy {
+                min_step,
+                max_step: min_step + extra,
+                allow_equal,
+                direction,
+                tolerance,
+            },
+        )
+    }
+
+    proptest! {
+        // The fast dampener has to agree with the brute force one on whether a level can be made safe and on how many
+        // readings that takes. Which readings it drops may differ, but they have to make the level safe.
+        #[test]
+        fn dampener_agrees_with_brute_force(
+            values in proptest::collection::vec(0..12usize, 0..10),
+            policy in any_policy(),
+        ) {
+            let level = Level { values };
+            let expected = level.evaluate_brute_force(&policy);
+            let dropped = level.evaluate(&policy);
+
+            prop_assert_eq!(level.is_safe_under(&policy), expected.is_some());
+            prop_assert_eq!(dropped.as_ref().map(Vec::len), expected.as_ref().map(Vec::len));
+
+            if let Some(dropped) = dropped {
+                let kept: Vec<usize> = level
+                    .values
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !dropped.contains(i))
+                    .map(|(_, &value)| value)
+                    .collect();
+                prop_assert!(policy.allows(&kept));
+            }
+        }
+    }
+}
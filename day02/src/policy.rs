@@ -0,0 +1,131 @@
+use std::fmt::{Display, Formatter};
+
+// Which way the readings of a level have to go. Either means: pick one, but stick to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Monotonicity {
+    Increasing,
+    Decreasing,
+    Either,
+}
+
+// What can be wrong with a single step between two neighbouring readings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    // The level was going up and now goes down, or the other way around (or the wrong way from the start).
+    DirectionFlip,
+    // Two neighbours with the same reading, when that isn't allowed.
+    ZeroStep,
+    StepTooSmall,
+    StepTooLarge,
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let text = match self {
+            Fault::DirectionFlip => "direction flip",
+            Fault::ZeroStep => "zero step",
+            Fault::StepTooSmall => "step too small",
+            Fault::StepTooLarge => "step too large",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+// What makes a level safe. The puzzle has two of these, but the sensors we look at have their own ideas about what a
+// sane step is, so everything can be tweaked:
+//
+// - every step between neighbours is at least `min_step` and at most `max_step`
+// - neighbours with the same reading are a step of zero, they're only fine with `allow_equal` (and then they don't say
+//   anything about the direction)
+// - all other steps go in the `direction`
+// - we may drop up to `tolerance` readings to get there (the problem dampener)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    pub min_step: usize,
+    pub max_step: usize,
+    pub allow_equal: bool,
+    pub direction: Monotonicity,
+    pub tolerance: usize,
+}
+
+impl SafetyPolicy {
+    // Part A: steps of 1 to 3, all up or all down, no second chances.
+    pub const STRICT: Self = Self {
+        min_step: 1,
+        max_step: 3,
+        allow_equal: false,
+        direction: Monotonicity::Either,
+        tolerance: 0,
+    };
+
+    // Part B: the same, but the problem dampener may drop a single reading.
+    pub const DAMPENED: Self = Self {
+        tolerance: 1,
+        ..Self::STRICT
+    };
+
+    pub fn with_tolerance(self, tolerance: usize) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    // Is a single step between two neighbours fine? For Either that depends on the steps before it, so the caller has
+    // to pass the direction it settled on so far. Returns the direction to use from here on.
+    pub fn step(&self, a: usize, b: usize, direction: Monotonicity) -> Option<Monotonicity> {
+        self.check_step(a, b, direction).ok()
+    }
+
+    // The same, but when the step isn't fine we also want to know what's wrong with it.
+    pub fn check_step(
+        &self,
+        a: usize,
+        b: usize,
+        direction: Monotonicity,
+    ) -> Result<Monotonicity, Fault> {
+        if a == b {
+            return if self.allow_equal {
+                Ok(direction)
+            } else {
+                Err(Fault::ZeroStep)
+            };
+        }
+
+        let step = a.abs_diff(b);
+        if step < self.min_step {
+            return Err(Fault::StepTooSmall);
+        }
+        if step > self.max_step {
+            return Err(Fault::StepTooLarge);
+        }
+
+        let going = if b > a {
+            Monotonicity::Increasing
+        } else {
+            Monotonicity::Decreasing
+        };
+        match direction {
+            Monotonicity::Either => Ok(going),
+            direction if direction == going => Ok(direction),
+            _ => Err(Fault::DirectionFlip),
+        }
+    }
+
+    // Are the readings safe as they are, so without dropping anything?
+    pub fn allows(&self, values: &[usize]) -> bool {
+        let mut direction = self.direction;
+        values
+            .windows(2)
+            .all(|w| match self.step(w[0], w[1], direction) {
+                Some(next) => {
+                    direction = next;
+                    true
+                }
+                None => false,
+            })
+    }
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        Self::STRICT
+    }
+}
@@ -0,0 +1,83 @@
+use harness::{ParseError, Solution};
+use std::{error::Error, fmt::Display};
+
+pub mod parser;
+pub mod scanner;
+pub mod vm;
+
+pub use parser::{Call, Instruction, Spanned};
+pub use vm::Vm;
+
+// Oh yeah how little did we know here when we decided a regex was better than building a parser. Now we have one: the
+// memory is scanned for calls once, part A and part B are just two machines running them. For memory that is too big
+// to keep around, `scanner::solve` runs both machines while scanning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Memory {
+    calls: Vec<Call>,
+}
+
+impl Memory {
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    // The calls that are instructions from the puzzle.
+    pub fn instructions(&self) -> Vec<Spanned<'_>> {
+        parser::parse(&self.calls)
+    }
+}
+
+// Corrupted memory is corrupted, anything we don't recognize is simply skipped. So there's no way for parsing to fail
+// here.
+pub fn parse(input: &str) -> Result<Memory, ParseError> {
+    Ok(Memory {
+        calls: parser::calls(input),
+    })
+}
+
+pub fn part_a(memory: &Memory) -> i64 {
+    Vm::part_a().run(&memory.calls)
+}
+
+pub fn part_b(memory: &Memory) -> i64 {
+    Vm::part_b().run(&memory.calls)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Memory;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(memory: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(memory))
+    }
+
+    fn part_b(memory: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_b(memory))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Part B has its own example, with some do() and don't() instructions thrown in.
+    const EXAMPLE_A: &str = include_str!("../../inputs/examples/day03-1.txt");
+    const EXAMPLE_B: &str = include_str!("../../inputs/examples/day03-2.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day03>(EXAMPLE_A).unwrap();
+        assert_eq!(answers.part_a, "161");
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day03>(EXAMPLE_B).unwrap();
+        assert_eq!(answers.part_b, "48");
+    }
+}
@@ -0,0 +1,136 @@
+use crate::scanner::Scanner;
+use std::ops::Range;
+
+// The corrupted memory is mostly garbage with a few instructions hidden in it. Instead of a regex that knows every
+// instruction (and a capture group per argument), the scanner looks for anything that looks like a call: a name, an
+// opening parenthesis, zero or more numbers separated by commas and a closing parenthesis. Only then we check if the
+// call is an instruction we know. The instructions of the puzzle have a variant of their own, any other name that a
+// machine knows (see `Vm::operation`) comes through as `Extra`, so the scanner doesn't care about either.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction<'a> {
+    Mul(usize, usize),
+    Do,
+    Dont,
+    // A call by a name that isn't from the puzzle, borrowed from the call it was decoded from.
+    Extra { name: &'a str, args: &'a [usize] },
+}
+
+// An instruction and where it is in the memory, as a range of bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned<'a> {
+    pub instruction: Instruction<'a>,
+    pub span: Range<usize>,
+}
+
+// Every name from the puzzle, we need them to find where a name starts (see `Call::resolve`).
+pub(crate) const NAMES: [&str; 3] = ["mul", "do", "don't"];
+
+// Something that looks like a call: a name and a list of numbers. The name is the whole name token, which can have
+// garbage glued to the front of it (xmul, undo), so the name of the call is the longest known name the token ends with.
+// Which names are known is up to whoever runs the calls, see `resolve`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub name: String,
+    pub args: Vec<usize>,
+    pub span: Range<usize>,
+}
+
+impl Call {
+    pub fn resolve<'n>(&self, names: impl IntoIterator<Item = &'n str>) -> Option<&'n str> {
+        names
+            .into_iter()
+            .filter(|name| self.name.ends_with(name))
+            .max_by_key(|name| name.len())
+    }
+
+    // The span of the call when it goes by the given name, so without the garbage in front of the name.
+    pub fn span_as(&self, name: &str) -> Range<usize> {
+        self.span.start + self.name.len() - name.len()..self.span.end
+    }
+}
+
+// Find all calls in the memory, in order.
+pub fn calls(memory: &str) -> Vec<Call> {
+    let mut calls = Vec::new();
+    Scanner::new().feed(memory.as_bytes(), |call| calls.push(call.clone()));
+    calls
+}
+
+// The calls that are instructions from the puzzle, in order.
+pub fn parse(calls: &[Call]) -> Vec<Spanned<'_>> {
+    calls.iter().filter_map(Spanned::decode).collect()
+}
+
+impl<'a> Spanned<'a> {
+    // Decode a call with only the names from the puzzle.
+    pub fn decode(call: &'a Call) -> Option<Self> {
+        Self::decode_with(call, [])
+    }
+
+    // Decode a call with some extra names on top of the ones from the puzzle. A name from the puzzle with the wrong
+    // arguments is corruption, even if there's an extra name it ends with.
+    pub fn decode_with<'n>(
+        call: &'a Call,
+        extra: impl IntoIterator<Item = &'n str>,
+    ) -> Option<Self> {
+        let name = call.resolve(NAMES.into_iter().chain(extra))?;
+        // The same name, but borrowed from the call, so the instruction doesn't depend on where the names came from.
+        let name = &call.name[call.name.len() - name.len()..];
+
+        let instruction = match (name, call.args.as_slice()) {
+            ("mul", &[x, y]) => Instruction::Mul(x, y),
+            ("do", []) => Instruction::Do,
+            ("don't", []) => Instruction::Dont,
+            (name, _) if NAMES.contains(&name) => return None,
+            (name, args) => Instruction::Extra { name, args },
+        };
+        Some(Spanned {
+            instruction,
+            span: call.span_as(name),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instructions_know_where_they_are() {
+        let memory = "xmul(2,4)&don't()_mul(5,5)+mul(32,64](undo()";
+
+        assert_eq!(
+            parse(&calls(memory)),
+            vec![
+                Spanned {
+                    instruction: Instruction::Mul(2, 4),
+                    span: 1..9
+                },
+                Spanned {
+                    instruction: Instruction::Dont,
+                    span: 10..17
+                },
+                Spanned {
+                    instruction: Instruction::Mul(5, 5),
+                    span: 18..26
+                },
+                Spanned {
+                    instruction: Instruction::Do,
+                    span: 40..44
+                },
+            ]
+        );
+        assert_eq!(&memory[40..44], "do()");
+    }
+
+    #[test]
+    fn almost_instructions_are_garbage() {
+        let garbage = "mul(4*mul(6,9!?(12,34)mul ( 2 , 4 )mul(1234,5)do(1)mul(1,2,3)mul(,)mul()";
+        assert_eq!(parse(&calls(garbage)), vec![]);
+
+        // A broken call doesn't hide the call right after it.
+        let memory = "mul(mul(1,2)";
+        assert_eq!(parse(&calls(memory))[0].instruction, Instruction::Mul(1, 2));
+    }
+}
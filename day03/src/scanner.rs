@@ -0,0 +1,216 @@
+use crate::{
+    parser::Call,
+    vm::{State, Vm},
+};
+use std::io::{self, Read};
+
+// The lexer and the parser in one, working a byte at a time. Because it remembers where it was between two calls to
+// `feed`, the memory can come in chunks of any size: a call that starts at the end of one chunk is finished in the
+// next. So we never need the whole memory at once, and a mul( split over two lines or two reads is no different from
+// any other.
+//
+// The grammar is that of a call: a name (letters and apostrophes, because of don't), an opening parenthesis, zero or
+// more numbers of at most three digits separated by commas and a closing parenthesis. Whenever a byte doesn't fit, the
+// call we were working on is garbage. That byte can still be the start of a new name though, so we look at it again as
+// if we were in the garbage. Nothing that fits in an argument list can start a name, so that's all it takes to never
+// miss a call.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Garbage,
+    Name,
+    // Right after the opening parenthesis.
+    Open,
+    Number,
+    // Right after a comma.
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    phase: Phase,
+    // How many bytes we've seen so far, for the spans.
+    offset: usize,
+    name: Vec<u8>,
+    name_end: usize,
+    max_name: usize,
+    args: Vec<usize>,
+    number: usize,
+    digits: usize,
+    // The call we hand out, filled in again for every call so its buffers are reused.
+    call: Call,
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scanner {
+    pub fn new() -> Self {
+        Self::with_max_name(usize::MAX)
+    }
+
+    // Names are resolved by the longest known name they end with (see `Call::resolve`), so if we know how long the
+    // longest name is we don't need to remember more of a name than that. Otherwise a long enough run of letters would
+    // eat all our memory.
+    pub fn with_max_name(max_name: usize) -> Self {
+        Self {
+            phase: Phase::Garbage,
+            offset: 0,
+            name: Vec::new(),
+            name_end: 0,
+            max_name,
+            args: Vec::new(),
+            number: 0,
+            digits: 0,
+            call: Call {
+                name: String::new(),
+                args: Vec::new(),
+                span: 0..0,
+            },
+        }
+    }
+
+    pub fn feed(&mut self, chunk: &[u8], mut on_call: impl FnMut(&Call)) {
+        for &byte in chunk {
+            while !self.accept(byte, &mut on_call) {
+                self.phase = Phase::Garbage;
+            }
+            self.offset += 1;
+        }
+    }
+
+    // Returns false when the byte doesn't fit in the call we're working on.
+    fn accept(&mut self, byte: u8, on_call: &mut impl FnMut(&Call)) -> bool {
+        let is_name = byte.is_ascii_alphabetic() || byte == b'\'';
+
+        match (self.phase, byte) {
+            (Phase::Garbage, _) if is_name => {
+                self.name.clear();
+                self.push_name(byte);
+                self.phase = Phase::Name;
+            }
+            (Phase::Garbage, _) => {}
+            (Phase::Name, _) if is_name => self.push_name(byte),
+            (Phase::Name, b'(') => {
+                self.name_end = self.offset;
+                self.args.clear();
+                self.phase = Phase::Open;
+            }
+            (Phase::Open, b')') => self.emit(on_call),
+            (Phase::Open | Phase::Comma, b'0'..=b'9') => {
+                self.number = (byte - b'0') as usize;
+                self.digits = 1;
+                self.phase = Phase::Number;
+            }
+            (Phase::Number, b'0'..=b'9') if self.digits < 3 => {
+                self.number = self.number * 10 + (byte - b'0') as usize;
+                self.digits += 1;
+            }
+            (Phase::Number, b',') => {
+                self.args.push(self.number);
+                self.phase = Phase::Comma;
+            }
+            (Phase::Number, b')') => {
+                self.args.push(self.number);
+                self.emit(on_call);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn push_name(&mut self, byte: u8) {
+        self.name.push(byte);
+        if self.name.len() > self.max_name {
+            self.name.remove(0);
+        }
+    }
+
+    // The closing parenthesis is the byte at the current offset. A name is only letters and apostrophes, so every byte
+    // of it is a char of its own.
+    fn emit(&mut self, on_call: &mut impl FnMut(&Call)) {
+        self.call.name.clear();
+        self.call
+            .name
+            .extend(self.name.iter().map(|&byte| byte as char));
+        self.call.args.clear();
+        self.call.args.extend_from_slice(&self.args);
+        self.call.span = self.name_end - self.name.len()..self.offset + 1;
+        self.phase = Phase::Garbage;
+        on_call(&self.call);
+    }
+}
+
+// Both parts in a single pass over the memory, whatever its size. The calls go straight into both machines, so we don't
+// keep anything around but the state of the machines.
+pub fn solve<R: Read>(mut reader: R) -> io::Result<(i64, i64)> {
+    let machines = [Vm::part_a(), Vm::part_b()];
+    let mut states = [State::default(); 2];
+
+    let max_name = machines.iter().map(Vm::max_name_len).max().unwrap_or(0);
+    let mut scanner = Scanner::with_max_name(max_name);
+
+    let mut buffer = vec![0; 1 << 16];
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        scanner.feed(&buffer[..read], |call| {
+            for (machine, state) in machines.iter().zip(states.iter_mut()) {
+                *state = machine.step(*state, call);
+            }
+        });
+    }
+
+    Ok((states[0].accumulator, states[1].accumulator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::calls;
+
+    const EXAMPLE_B: &str = include_str!("../../inputs/examples/day03-2.txt");
+
+    #[test]
+    fn chunks_of_any_size() {
+        let expected = calls(EXAMPLE_B);
+        assert_eq!(expected.len(), 6);
+
+        for size in 1..=EXAMPLE_B.len() {
+            let mut found = Vec::new();
+            let mut scanner = Scanner::new();
+            for chunk in EXAMPLE_B.as_bytes().chunks(size) {
+                scanner.feed(chunk, |call| found.push(call.clone()));
+            }
+            assert_eq!(found, expected, "chunks of {} bytes", size);
+        }
+    }
+
+    #[test]
+    fn both_parts_in_one_pass() {
+        assert_eq!(solve(EXAMPLE_B.as_bytes()).unwrap(), (161, 48));
+
+        // A call split over two reads.
+        let reader = "don't()mul(1"
+            .as_bytes()
+            .chain("2,3)do()mul(007,2)".as_bytes());
+        assert_eq!(solve(reader).unwrap(), (50, 14));
+    }
+
+    #[test]
+    fn long_names_are_cut_short() {
+        let mut found = Vec::new();
+        Scanner::with_max_name(3).feed(b"xxxxxmul(2,3)", |call| found.push(call.clone()));
+
+        assert_eq!(found[0].name, "mul");
+        assert_eq!(found[0].span, 5..13);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::parser::{Call, Instruction, Spanned, NAMES};
+use std::collections::HashMap;
+
+// A tiny machine to run the instructions we find in the memory. It has an accumulator that starts at zero and a flag
+// that says if it's enabled, which it is at the start. The machine runs the decoded instructions (see
+// `Spanned::decode`): mul adds a product to the accumulator, and do() and don't() turn the machine on and off, but only
+// for a machine that listens to them (part B does, part A doesn't). On top of that we can register extra opcodes, which
+// come out of the decoder as `Instruction::Extra`:
+//
+// - an operation takes the accumulator and the arguments and returns the new accumulator, but only when the machine is
+//   enabled
+// - a switch turns the machine on or off
+//
+// A call with a name we don't know, or with the wrong number of arguments, is corruption and simply skipped.
+
+pub type Operation = Box<dyn Fn(i64, &[usize]) -> i64>;
+
+enum Opcode {
+    Operation { arity: usize, operation: Operation },
+    Switch { enables: bool },
+}
+
+#[derive(Default)]
+pub struct Vm {
+    conditional: bool,
+    opcodes: HashMap<String, Opcode>,
+}
+
+// Where the machine is after running some instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    pub accumulator: i64,
+    pub enabled: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            accumulator: 0,
+            enabled: true,
+        }
+    }
+}
+
+impl Vm {
+    // A machine that runs mul and nothing else.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn part_a() -> Self {
+        Self::new()
+    }
+
+    pub fn part_b() -> Self {
+        Self::part_a().conditional()
+    }
+
+    // Listen to do() and don't().
+    pub fn conditional(mut self) -> Self {
+        self.conditional = true;
+        self
+    }
+
+    // Register an operation with a fixed number of arguments. The operation is only called with exactly that many. The
+    // names from the puzzle are taken, registering one of those does nothing.
+    pub fn operation<F>(mut self, name: &str, arity: usize, operation: F) -> Self
+    where
+        F: Fn(i64, &[usize]) -> i64 + 'static,
+    {
+        let operation = Box::new(operation);
+        self.opcodes
+            .insert(name.to_string(), Opcode::Operation { arity, operation });
+        self
+    }
+
+    // Register a call without arguments that enables or disables the machine.
+    pub fn switch(mut self, name: &str, enables: bool) -> Self {
+        self.opcodes
+            .insert(name.to_string(), Opcode::Switch { enables });
+        self
+    }
+
+    // The longest name we know, the scanner doesn't need to remember more of a name than this.
+    pub fn max_name_len(&self) -> usize {
+        NAMES
+            .into_iter()
+            .chain(self.opcodes.keys().map(String::as_str))
+            .map(str::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // The instruction a call is for this machine, if any.
+    pub fn decode<'c>(&self, call: &'c Call) -> Option<Spanned<'c>> {
+        Spanned::decode_with(call, self.opcodes.keys().map(String::as_str))
+    }
+
+    pub fn run(&self, calls: &[Call]) -> i64 {
+        calls
+            .iter()
+            .fold(State::default(), |state, call| self.step(state, call))
+            .accumulator
+    }
+
+    pub fn step(&self, state: State, call: &Call) -> State {
+        match self.decode(call) {
+            Some(spanned) => self.execute(state, &spanned.instruction),
+            None => state,
+        }
+    }
+
+    pub fn execute(&self, state: State, instruction: &Instruction) -> State {
+        match *instruction {
+            Instruction::Mul(x, y) if state.enabled => State {
+                accumulator: state.accumulator + (x * y) as i64,
+                ..state
+            },
+            Instruction::Do if self.conditional => State {
+                enabled: true,
+                ..state
+            },
+            Instruction::Dont if self.conditional => State {
+                enabled: false,
+                ..state
+            },
+            Instruction::Extra { name, args } => match self.opcodes.get(name) {
+                Some(Opcode::Switch { enables }) if args.is_empty() => State {
+                    enabled: *enables,
+                    ..state
+                },
+                Some(Opcode::Operation { arity, operation })
+                    if args.len() == *arity && state.enabled =>
+                {
+                    State {
+                        accumulator: operation(state.accumulator, args),
+                        ..state
+                    }
+                }
+                _ => state,
+            },
+            _ => state,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::calls;
+
+    #[test]
+    fn extra_opcodes() {
+        let memory = calls("mul(2,3)xadd(1,2)sub(10)don't()sub(1)_do()sub(4)neg()add(1)mul(2)");
+
+        let vm = Vm::part_b()
+            .operation("add", 2, |accumulator, args| {
+                accumulator + (args[0] + args[1]) as i64
+            })
+            .operation("sub", 1, |accumulator, args| accumulator - args[0] as i64)
+            .operation("neg", 0, |accumulator, _| -accumulator);
+
+        // 6 + 3 - 10, skip the sub(1) while disabled, -4, negate, and the add and mul have too few arguments.
+        assert_eq!(vm.run(&memory), 5);
+
+        // Part A only knows mul, so only the first call counts.
+        assert_eq!(Vm::part_a().run(&memory), 6);
+    }
+
+    #[test]
+    fn instructions_from_the_puzzle_come_first() {
+        let memory = calls("xadd(1,2)mul(2,3)mul(4)");
+        let vm = Vm::new()
+            .operation("add", 2, |accumulator, args| {
+                accumulator + (args[0] + args[1]) as i64
+            })
+            .operation("mul", 1, |_, _| panic!("mul is taken"));
+
+        assert_eq!(
+            vm.decode(&memory[0]),
+            Some(Spanned {
+                instruction: Instruction::Extra {
+                    name: "add",
+                    args: &[1, 2]
+                },
+                span: 1..9
+            })
+        );
+        assert_eq!(
+            vm.decode(&memory[1]).unwrap().instruction,
+            Instruction::Mul(2, 3)
+        );
+        // A mul with one argument is a broken mul, not the mul we registered.
+        assert_eq!(vm.decode(&memory[2]), None);
+        assert_eq!(vm.run(&memory), 9);
+    }
+}
@@ -0,0 +1,115 @@
+use grid::Grid;
+use harness::{ParseError, Solution};
+use search::{Found, Searcher};
+use std::{error::Error, fmt::Display, str::FromStr};
+use stencil::{Match, Stencil};
+
+pub mod search;
+pub mod stencil;
+pub mod stream;
+
+// The X-MAS of part B isn't code anymore, it's a pattern in a file. See the stencil module for what goes in there.
+pub const X_MAS: &str = include_str!("../patterns/x-mas.txt");
+
+#[derive(Debug, Clone)]
+pub struct WordSearch {
+    grid: Grid<u8>,
+}
+
+impl WordSearch {
+    // Every occurrence of any of the words, in all eight directions. See the search module for how.
+    pub fn find<W: AsRef<[u8]>>(&self, words: &[W]) -> Vec<Found> {
+        Searcher::new(words).find_all(&self.grid)
+    }
+
+    // XMAS used to have its own loop over the grid, now it's just a word like any other.
+    pub fn count_xmas(&self) -> usize {
+        self.find(&["XMAS"]).len()
+    }
+
+    // Every match of a 2D pattern, in all of its rotations and reflections.
+    pub fn find_stencil(&self, stencil: &Stencil) -> Vec<Match> {
+        stencil.find_all(&self.grid)
+    }
+
+    pub fn count_x_mas(&self) -> usize {
+        let stencil: Stencil = X_MAS.parse().expect("the X-MAS pattern is a valid stencil");
+        self.find_stencil(&stencil).len()
+    }
+}
+
+// Convert the input into a grid with padding. This used to be a From<String>, but FromStr is the trait to use when
+// converting text into our own types: it can fail and it gives us `input.parse()` for free. A word search can't really
+// fail though, any letter is fine. Both the word search and the stencils stay inside the puzzle by themselves, so the
+// grid doesn't need any padding.
+impl FromStr for WordSearch {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            grid: Grid::parse(input, 0, b'.'),
+        })
+    }
+}
+
+// Here the FromStr trait is used to convert the input into a grid. Rust infers the type to parse into from the return
+// type.
+pub fn parse(input: &str) -> Result<WordSearch, ParseError> {
+    input.parse()
+}
+
+pub fn part_a(word_search: &WordSearch) -> usize {
+    word_search.count_xmas()
+}
+
+pub fn part_b(word_search: &WordSearch) -> usize {
+    word_search.count_x_mas()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = WordSearch;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(word_search: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(word_search))
+    }
+
+    fn part_b(word_search: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_b(word_search))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day04-1.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day04>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_a, "18");
+    }
+
+    #[test]
+    fn any_words() {
+        let word_search = parse(EXAMPLE).unwrap();
+        let found = word_search.find(&["XMAS", "SAMX", "MAS"]);
+
+        // Every XMAS is also a SAMX read the other way around, and it has a MAS in it.
+        let count = |word| found.iter().filter(|f| f.word == word).count();
+        assert_eq!((count(0), count(1)), (18, 18));
+        assert!(count(2) >= 18);
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day04>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_b, "9");
+    }
+}
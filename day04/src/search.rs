@@ -0,0 +1,217 @@
+use aho_corasick::{
+    automaton::{Automaton, StateID},
+    dfa::DFA,
+    Anchored,
+};
+use grid::{Coordinate, Direction, Grid};
+
+// A word search for any number of words at once. Instead of trying every word in every direction from every cell, we
+// cut the grid into lines: every row, every column and both diagonals. A word in any of the eight directions is then a
+// word somewhere in one of those lines, read either forwards or backwards. So we search the lines for the words and for
+// the words reversed, all in one go with an Aho-Corasick automaton. That's a single pass over every line, whether we
+// look for one word or for a few dozen.
+//
+// We drive the automaton ourselves, a letter at a time. All it needs to remember about a line is a single state, so a
+// line can just as well be read in bits, like a column of a grid that comes in row by row (see the stream module).
+
+// Where a word was found: the cell with its first letter and the direction it's read in. The word is an index into the
+// words the searcher was built with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Found {
+    pub word: usize,
+    pub start: Coordinate,
+    pub direction: Direction,
+}
+
+#[derive(Debug, Clone)]
+pub struct Searcher {
+    automaton: DFA,
+    // For every pattern in the automaton the word it belongs to and if it's that word reversed.
+    patterns: Vec<(usize, bool)>,
+    max_len: usize,
+}
+
+// A line through the grid: where it starts, which way it goes and the letters on it.
+struct Line {
+    start: Coordinate,
+    direction: Direction,
+    letters: Vec<u8>,
+}
+
+// The other four directions are these read backwards.
+const FORWARDS: [Direction; 4] = [
+    Direction::Right,
+    Direction::DownRight,
+    Direction::Down,
+    Direction::DownLeft,
+];
+
+impl Searcher {
+    pub fn new<W: AsRef<[u8]>>(words: &[W]) -> Self {
+        // A palindrome is the same pattern twice, but it still counts for both directions, so it stays in twice. The
+        // automaton reports every pattern that matches, duplicates included. An empty word would match everywhere and
+        // nowhere at the same time, so it's left out and never found. It keeps its index though, so the other words
+        // keep theirs.
+        let mut patterns = Vec::new();
+        let mut bytes = Vec::new();
+        for (word, letters) in words.iter().enumerate() {
+            let letters = letters.as_ref();
+            if letters.is_empty() {
+                continue;
+            }
+            patterns.push((word, false));
+            bytes.push(letters.to_vec());
+            patterns.push((word, true));
+            bytes.push(letters.iter().rev().copied().collect());
+        }
+
+        Self {
+            max_len: bytes.iter().map(Vec::len).max().unwrap_or(0),
+            automaton: DFA::new(bytes).expect("words are small enough for an automaton"),
+            patterns,
+        }
+    }
+
+    // Every occurrence of every word, in any of the eight directions. Only the puzzle itself is searched, not the
+    // padding around it.
+    pub fn find_all(&self, grid: &Grid<u8>) -> Vec<Found> {
+        let mut found = Vec::new();
+        for line in lines(grid) {
+            let mut state = self.start();
+            for (i, &letter) in line.letters.iter().enumerate() {
+                state = self.next(state, letter);
+                let end = line.start.steps(line.direction, i);
+                found.extend(self.found_at(state, end, line.direction));
+            }
+        }
+        found
+    }
+
+    // The longest word, a word can't be spread over more rows than this.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    // The state of a line before its first letter.
+    pub(crate) fn start(&self) -> StateID {
+        self.automaton
+            .start_state(Anchored::No)
+            .expect("the automaton searches anywhere in a line")
+    }
+
+    // The state of a line after reading one more letter.
+    pub(crate) fn next(&self, state: StateID, letter: u8) -> StateID {
+        self.automaton.next_state(Anchored::No, state, letter)
+    }
+
+    // Every word that ends with the letter just read, which is at `end` on a line going in `direction`.
+    pub(crate) fn found_at(
+        &self,
+        state: StateID,
+        end: Coordinate,
+        direction: Direction,
+    ) -> impl Iterator<Item = Found> + '_ {
+        let matches = if self.automaton.is_match(state) {
+            self.automaton.match_len(state)
+        } else {
+            0
+        };
+
+        (0..matches).map(move |index| {
+            let pattern = self.automaton.match_pattern(state, index);
+            let (word, reversed) = self.patterns[pattern.as_usize()];
+
+            // A reversed word starts where the match ends and is read in the opposite direction.
+            if reversed {
+                Found {
+                    word,
+                    start: end,
+                    direction: direction.opposite(),
+                }
+            } else {
+                let len = self.automaton.pattern_len(pattern);
+                Found {
+                    word,
+                    start: end.steps(direction.opposite(), len - 1),
+                    direction,
+                }
+            }
+        })
+    }
+}
+
+// Every line through the puzzle in the four forward directions. A line starts at a cell whose neighbour behind it isn't
+// part of the puzzle anymore, and runs until it leaves the puzzle on the other side.
+fn lines(grid: &Grid<u8>) -> impl Iterator<Item = Line> + '_ {
+    let padding = grid.padding();
+    let in_puzzle = move |coordinate: Coordinate| {
+        (padding..grid.row_size() - padding).contains(&coordinate.row)
+            && (padding..grid.col_size() - padding).contains(&coordinate.col)
+    };
+
+    FORWARDS.into_iter().flat_map(move |direction| {
+        grid.coordinates()
+            .filter(move |&start| !in_puzzle(start.step(direction.opposite())))
+            .map(move |start| {
+                let letters = (0..)
+                    .map(|i| start.steps(direction, i))
+                    .take_while(|&coordinate| in_puzzle(coordinate))
+                    .map(|coordinate| grid[coordinate])
+                    .collect();
+                Line {
+                    start,
+                    direction,
+                    letters,
+                }
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_in_every_direction() {
+        // CAT once in every direction, all starting from the C in the middle.
+        let grid = Grid::parse("T.T.T\n.AAA.\nTACAT\n.AAA.\nT.T.T\n", 1, b'.');
+
+        let found = Searcher::new(&["CAT"]).find_all(&grid);
+        assert_eq!(found.len(), 8);
+        assert!(found.iter().all(|f| f.start == Coordinate::new(3, 3)));
+        for direction in Direction::ALL {
+            assert!(found.iter().any(|f| f.direction == direction));
+        }
+    }
+
+    #[test]
+    fn empty_words_are_never_found() {
+        let grid = Grid::parse("AB\n", 0, b'.');
+        let found = Searcher::new(&["", "AB"]).find_all(&grid);
+
+        assert_eq!(
+            found,
+            vec![Found {
+                word: 1,
+                start: Coordinate::new(0, 0),
+                direction: Direction::Right,
+            }]
+        );
+    }
+
+    #[test]
+    fn many_words_and_palindromes() {
+        let grid = Grid::parse("ABBA\nXYZQ\n", 0, b'.');
+        let found = Searcher::new(&["ABBA", "BB", "ZY", "Q"]).find_all(&grid);
+
+        let count = |word| found.iter().filter(|f| f.word == word).count();
+        // A palindrome reads the same both ways, so it's there twice. A single letter is there in all four lines
+        // through it, both ways.
+        assert_eq!((count(0), count(1), count(2), count(3)), (2, 2, 1, 8));
+        assert!(found.contains(&Found {
+            word: 2,
+            start: Coordinate::new(1, 2),
+            direction: Direction::Left,
+        }));
+    }
+}
@@ -0,0 +1,195 @@
+use grid::{Coordinate, Grid};
+use harness::ParseError;
+use std::str::FromStr;
+
+// A 2D pattern to look for in a grid, like the X-MAS of part B:
+//
+//     M.S
+//     .A.
+//     M.S
+//
+// A dot is a wildcard, anything else has to be there exactly. Rows are separated by newlines or by slashes, so the X-MAS
+// is just as well `M.S/.A./M.S`. A cell is a single byte of the grid, so patterns are ASCII only. A stencil matches in
+// any of its rotations and reflections, so the one above also finds the X-MAS with both M's on top, or on the right.
+// That's what the hand-written check with its four variations used to do, now any pattern gets it for free.
+
+const WILDCARD: u8 = b'.';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stencil {
+    // Every rotation and reflection that is different from the others, the pattern as written first. A cell is None
+    // when it's a wildcard.
+    variants: Vec<Vec<Vec<Option<u8>>>>,
+}
+
+// Where a stencil matched: the top left corner of the variant that matched, and which variant that was. The corner is
+// in grid coordinates, so including the padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    pub variant: usize,
+    pub at: Coordinate,
+}
+
+impl Stencil {
+    pub fn variants(&self) -> usize {
+        self.variants.len()
+    }
+
+    // Every match of every variant. A variant only matches where it fits completely inside the puzzle, the padding is
+    // never part of a match.
+    pub fn find_all(&self, grid: &Grid<u8>) -> Vec<Match> {
+        let padding = grid.padding();
+        let (rows, cols) = (grid.row_size() - 2 * padding, grid.col_size() - 2 * padding);
+
+        let mut matches = Vec::new();
+        for (variant, cells) in self.variants.iter().enumerate() {
+            let (height, width) = (cells.len(), cells[0].len());
+            if height > rows || width > cols {
+                continue;
+            }
+
+            for row in padding..=padding + rows - height {
+                for col in padding..=padding + cols - width {
+                    let fits = cells.iter().enumerate().all(|(r, line)| {
+                        line.iter().enumerate().all(|(c, cell)| match cell {
+                            Some(letter) => grid[Coordinate::new(row + r, col + c)] == *letter,
+                            None => true,
+                        })
+                    });
+                    if fits {
+                        matches.push(Match {
+                            variant,
+                            at: Coordinate::new(row, col),
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+}
+
+// A quarter turn clockwise: the first column read from the bottom up becomes the first row.
+fn rotate<T: Copy>(cells: &[Vec<T>]) -> Vec<Vec<T>> {
+    (0..cells[0].len())
+        .map(|col| cells.iter().rev().map(|row| row[col]).collect())
+        .collect()
+}
+
+// Mirror left to right.
+fn reflect<T: Copy>(cells: &[Vec<T>]) -> Vec<Vec<T>> {
+    cells
+        .iter()
+        .map(|row| row.iter().rev().copied().collect())
+        .collect()
+}
+
+impl FromStr for Stencil {
+    type Err = ParseError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = pattern
+            .split(['/', '\n'])
+            .map(str::trim_end)
+            .filter(|row| !row.is_empty())
+            .collect();
+
+        let Some(width) = rows.first().map(|row| row.len()) else {
+            return Err(ParseError::new(1, "", "expected at least one row"));
+        };
+        // Widths are in bytes, so anything that isn't ASCII has to go before we look at them.
+        for (index, row) in rows.iter().enumerate() {
+            if let Some((col, c)) = row.char_indices().find(|(_, c)| !c.is_ascii()) {
+                let cell = &row[col..col + c.len_utf8()];
+                let error = ParseError::in_line(row, cell, "a cell has to be an ASCII character");
+                return Err(error.on_line(index + 1));
+            }
+        }
+        for (index, row) in rows.iter().enumerate() {
+            let reason = format!("expected {} cells like the first row", width);
+            if row.len() > width {
+                return Err(ParseError::in_line(row, &row[width..], reason).on_line(index + 1));
+            } else if row.len() < width {
+                return Err(ParseError::at_end(row, reason).on_line(index + 1));
+            }
+        }
+
+        let cells: Vec<Vec<Option<u8>>> = rows
+            .iter()
+            .map(|row| {
+                row.bytes()
+                    .map(|cell| (cell != WILDCARD).then_some(cell))
+                    .collect()
+            })
+            .collect();
+
+        // The four rotations and the four rotations of the mirror image. Symmetric patterns give the same variant more
+        // than once, we keep only one of each so a match isn't counted twice.
+        let mut variants: Vec<Vec<Vec<Option<u8>>>> = Vec::with_capacity(8);
+        for mut variant in [cells.clone(), reflect(&cells)] {
+            for _ in 0..4 {
+                if !variants.contains(&variant) {
+                    variants.push(variant.clone());
+                }
+                variant = rotate(&variant);
+            }
+        }
+
+        Ok(Self { variants })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotations_and_reflections() {
+        let x_mas: Stencil = "M.S/.A./M.S".parse().unwrap();
+        assert_eq!(x_mas.variants(), 4);
+
+        // Nothing to turn or mirror here.
+        let a: Stencil = "A".parse().unwrap();
+        assert_eq!(a.variants(), 1);
+
+        // Every L is different.
+        let l: Stencil = "X./XX\n".parse().unwrap();
+        assert_eq!(l.variants(), 4);
+        let l: Stencil = "X../XXX".parse().unwrap();
+        assert_eq!(l.variants(), 8);
+    }
+
+    #[test]
+    fn matches_fit_in_the_puzzle() {
+        let grid = Grid::parse("SAS\nXAX\nMAM\n", 1, b'.');
+        let stencil: Stencil = "M.M".parse().unwrap();
+
+        // Only the bottom row, the vertical variants don't fit and the padding never matches.
+        assert_eq!(
+            stencil.find_all(&grid),
+            vec![Match {
+                variant: 0,
+                at: Coordinate::new(3, 1)
+            }]
+        );
+    }
+
+    #[test]
+    fn rows_must_be_equally_long() {
+        let error = "M.S/.A/M.S".parse::<Stencil>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected 3 cells like the first row"
+        );
+        assert!("".parse::<Stencil>().is_err());
+    }
+
+    #[test]
+    fn cells_are_ascii() {
+        let error = "ab\ncéd".parse::<Stencil>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: a cell has to be an ASCII character (found \"é\")"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::search::{Found, Searcher};
+use aho_corasick::automaton::StateID;
+use grid::{Coordinate, Direction};
+use std::io::{self, BufRead};
+
+// The word search for grids that don't fit in memory. The automaton reads every line through the grid a letter at a
+// time and only needs to know the state the line was in before (see `Searcher::next`). A line that goes down comes from
+// a cell in the row above, so all we keep between two rows is the state of every column and both diagonals in the last
+// row: three states per column, no matter how many rows there are. A word is found as soon as its last letter has been
+// read, and only once, because every line through a cell reads it only once.
+//
+// Rows don't have to be equally long. A cell past the end of its row simply isn't there, and a line through it starts
+// over after it. Coordinates are the row and column in the input, both starting at zero.
+
+// Read right, every row is a line of its own.
+const ACROSS: Direction = Direction::Right;
+
+// The other three directions come from the row above. The other four are these read backwards.
+const DOWNWARDS: [Direction; 3] = [Direction::DownRight, Direction::Down, Direction::DownLeft];
+
+pub fn find<R: BufRead>(
+    searcher: &Searcher,
+    mut reader: R,
+    mut on_found: impl FnMut(Found),
+) -> io::Result<()> {
+    let start = searcher.start();
+
+    // For every direction the state of the line through every cell of the row above, and of the row we're reading.
+    let mut above: [Vec<StateID>; 3] = Default::default();
+    let mut below: [Vec<StateID>; 3] = Default::default();
+    let mut letters = Vec::new();
+
+    for row in 0.. {
+        letters.clear();
+        if reader.read_until(b'\n', &mut letters)? == 0 {
+            break;
+        }
+        if letters.last() == Some(&b'\n') {
+            letters.pop();
+        }
+        if letters.last() == Some(&b'\r') {
+            letters.pop();
+        }
+
+        let mut across = start;
+        for (col, &letter) in letters.iter().enumerate() {
+            let end = Coordinate::new(row, col);
+            across = searcher.next(across, letter);
+            searcher
+                .found_at(across, end, ACROSS)
+                .for_each(&mut on_found);
+
+            for (index, direction) in DOWNWARDS.into_iter().enumerate() {
+                // The cell the line comes from, which might be off the grid or past the end of its row.
+                let (_, col_delta) = direction.delta();
+                let from = col
+                    .checked_add_signed(-col_delta)
+                    .and_then(|from| above[index].get(from))
+                    .copied()
+                    .unwrap_or(start);
+                let state = searcher.next(from, letter);
+                searcher
+                    .found_at(state, end, direction)
+                    .for_each(&mut on_found);
+                below[index].push(state);
+            }
+        }
+
+        std::mem::swap(&mut above, &mut below);
+        below.iter_mut().for_each(Vec::clear);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::Grid;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day04-1.txt");
+
+    fn streamed(searcher: &Searcher, input: &str) -> Vec<Found> {
+        let mut found = Vec::new();
+        find(searcher, input.as_bytes(), |f| found.push(f)).unwrap();
+        found.sort_by_key(|f| (f.word, f.start, f.direction as usize));
+        found
+    }
+
+    fn in_memory(searcher: &Searcher, input: &str) -> Vec<Found> {
+        let mut found = searcher.find_all(&Grid::parse(input, 0, b'.'));
+        found.sort_by_key(|f| (f.word, f.start, f.direction as usize));
+        found
+    }
+
+    #[test]
+    fn same_as_in_memory() {
+        let searcher = Searcher::new(&["XMAS", "MAS", "X", "SAMXMAS"]);
+        let found = streamed(&searcher, EXAMPLE);
+
+        assert_eq!(found.iter().filter(|f| f.word == 0).count(), 18);
+        assert_eq!(found, in_memory(&searcher, EXAMPLE));
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let input = "XMAS\nM\nAMAS\r\n\nSAMX\nXM\nA\nSAMX";
+        let searcher = Searcher::new(&["XMAS", "AM"]);
+
+        assert_eq!(streamed(&searcher, input), in_memory(&searcher, input));
+    }
+}
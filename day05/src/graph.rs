@@ -0,0 +1,241 @@
+use crate::Rule;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+// The rules as a graph: an edge from every page to every page that has to come after it. Checking a rule is looking up a
+// page in a set, and putting pages in order is a topological sort of the pages in the update.
+//
+// The rules as a whole don't have to be acyclic, and in the real input they aren't. Only the rules between the pages of
+// a single update have to be, otherwise there is no right order. That's what the sort checks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleGraph {
+    successors: HashMap<usize, HashSet<usize>>,
+}
+
+// Pages that have to come before each other in a circle, every page before the next one and the last one before the
+// first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub pages: Vec<usize>,
+}
+
+impl RuleGraph {
+    pub fn new(rules: &[Rule]) -> Self {
+        let mut successors: HashMap<usize, HashSet<usize>> = HashMap::new();
+        for rule in rules {
+            successors.entry(rule.left).or_default().insert(rule.right);
+        }
+        Self { successors }
+    }
+
+    // Is there a rule that says `left` has to be printed before `right`?
+    pub fn has_rule(&self, left: usize, right: usize) -> bool {
+        self.successors
+            .get(&left)
+            .is_some_and(|successors| successors.contains(&right))
+    }
+
+    // Kahn's algorithm: a page can go next once all pages that have to be before it are placed. Pages that are free to
+    // go at the same time keep the order they had in the update, so an update that is already right stays the same.
+    // Pages are told apart by their number, so they have to be different pages, which parsing an update makes sure of.
+    pub fn sort(&self, pages: &[usize]) -> Result<Vec<usize>, Cycle> {
+        self.sort_keeping(pages, &[])
+    }
+
+    // The same, but the pages in `keep` also stay in the order they're given in, as if there were rules between them.
+    // That's only possible if that order doesn't break any rules, which is up to the caller. A cycle that goes through
+    // the kept order instead of only through rules can't be named as a `Cycle`, so this stays inside the crate, where
+    // the only caller is the repair and it only keeps pages that fit the rules.
+    pub(crate) fn sort_keeping(
+        &self,
+        pages: &[usize],
+        keep: &[usize],
+    ) -> Result<Vec<usize>, Cycle> {
+        let position: HashMap<usize, usize> = pages
+            .iter()
+            .enumerate()
+            .map(|(index, &page)| (page, index))
+            .collect();
+        let next_kept: HashMap<usize, usize> =
+            keep.windows(2).map(|pair| (pair[0], pair[1])).collect();
+
+        // Only the rules between pages of this update matter.
+        let successors = |page: usize| {
+            self.successors
+                .get(&page)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|successor| position.contains_key(successor))
+                .chain(next_kept.get(&page).copied())
+        };
+
+        let mut in_degree: HashMap<usize, usize> = position.keys().map(|&page| (page, 0)).collect();
+        for &page in position.keys() {
+            for successor in successors(page) {
+                *in_degree.get_mut(&successor).unwrap() += 1;
+            }
+        }
+
+        // The page that was first in the update goes first of all pages that are ready. With a plain queue a page that
+        // becomes ready later would have to wait for every page that was ready before, even pages that came after it.
+        let mut ready: BinaryHeap<Reverse<(usize, usize)>> = position
+            .iter()
+            .filter(|(page, _)| in_degree[page] == 0)
+            .map(|(&page, &index)| Reverse((index, page)))
+            .collect();
+
+        let mut sorted = Vec::with_capacity(position.len());
+        while let Some(Reverse((_, page))) = ready.pop() {
+            sorted.push(page);
+
+            for successor in successors(page) {
+                let degree = in_degree.get_mut(&successor).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(Reverse((position[&successor], successor)));
+                }
+            }
+        }
+
+        if sorted.len() == position.len() {
+            Ok(sorted)
+        } else {
+            Err(self.find_cycle(&in_degree))
+        }
+    }
+
+    // Which pages of the update have to come before which, not just by a rule of their own but also through other pages
+    // of the update: `before[i][j]` says the page at `i` has to come before the page at `j`. The closure only goes
+    // through pages of the update, the rules for pages that aren't printed don't count.
+    pub fn closure(&self, pages: &[usize]) -> Vec<Vec<bool>> {
+        let n = pages.len();
+        let mut before: Vec<Vec<bool>> = pages
+            .iter()
+            .map(|&left| {
+                pages
+                    .iter()
+                    .map(|&right| self.has_rule(left, right))
+                    .collect()
+            })
+            .collect();
+
+        // Floyd-Warshall, updates are only a couple of pages long.
+        for k in 0..n {
+            let through = before[k].clone();
+            for row in before.iter_mut().filter(|row| row[k]) {
+                for (cell, &via) in row.iter_mut().zip(&through) {
+                    *cell |= via;
+                }
+            }
+        }
+        before
+    }
+
+    // The pages that are left after the sort all still wait for some other page that is left, so walking back from any
+    // of them to a page it waits for, we end up going round in circles. The circle we end up in is the cycle.
+    fn find_cycle(&self, in_degree: &HashMap<usize, usize>) -> Cycle {
+        let left: HashSet<usize> = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree > 0)
+            .map(|(&page, _)| page)
+            .collect();
+
+        let mut page = *left.iter().min().expect("a failed sort leaves pages");
+        let mut path = vec![page];
+        loop {
+            page = *left
+                .iter()
+                .filter(|&&before| self.has_rule(before, page))
+                .min()
+                .expect("a page that is left waits for another page that is left");
+
+            if let Some(start) = path.iter().position(|&seen| seen == page) {
+                // We walked backwards, so turn it around to get the order of the rules. And start at the lowest page,
+                // so the same cycle always reads the same.
+                let mut pages = path.split_off(start);
+                pages.reverse();
+                let lowest = (0..pages.len()).min_by_key(|&i| pages[i]).unwrap();
+                pages.rotate_left(lowest);
+                return Cycle { pages };
+            }
+            path.push(page);
+        }
+    }
+}
+
+// Rules written as pairs, for the tests of every module.
+#[cfg(test)]
+impl RuleGraph {
+    pub(crate) fn from_pairs(rules: &[(usize, usize)]) -> Self {
+        let rules: Vec<Rule> = rules
+            .iter()
+            .map(|&(left, right)| Rule { left, right })
+            .collect();
+        Self::new(&rules)
+    }
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for page in &self.pages {
+            write!(f, "{} -> ", page)?;
+        }
+        write!(f, "{}", self.pages[0])
+    }
+}
+
+impl Error for Cycle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_keeps_the_order_where_it_can() {
+        let graph = RuleGraph::from_pairs(&[(1, 2), (2, 3), (4, 3)]);
+
+        assert_eq!(graph.sort(&[3, 2, 1]), Ok(vec![1, 2, 3]));
+        assert_eq!(graph.sort(&[3, 4, 1, 2]), Ok(vec![4, 1, 2, 3]));
+        // Pages without any rules between them stay where they are.
+        assert_eq!(graph.sort(&[5, 6]), Ok(vec![5, 6]));
+    }
+
+    #[test]
+    fn valid_updates_stay_the_same() {
+        // 2 doesn't care where it is, so the update is fine as it is, even though 3 only becomes free after 1.
+        let graph = RuleGraph::from_pairs(&[(1, 3)]);
+
+        assert_eq!(graph.sort(&[1, 3, 2]), Ok(vec![1, 3, 2]));
+        assert_eq!(graph.sort(&[3, 1, 2]), Ok(vec![1, 3, 2]));
+        assert_eq!(graph.sort(&[2, 3, 1]), Ok(vec![2, 1, 3]));
+    }
+
+    #[test]
+    fn cycles_are_named() {
+        let graph = RuleGraph::from_pairs(&[(1, 2), (2, 3), (3, 1), (3, 4), (0, 1)]);
+
+        let cycle = graph.sort(&[4, 3, 2, 1, 0]).unwrap_err();
+        assert_eq!(cycle.to_string(), "1 -> 2 -> 3 -> 1");
+
+        // Without 2 the cycle isn't part of the update, so it doesn't matter.
+        assert_eq!(graph.sort(&[4, 3, 1]), Ok(vec![3, 4, 1]));
+    }
+
+    #[test]
+    fn closure_goes_through_the_update_only() {
+        let graph = RuleGraph::from_pairs(&[(1, 2), (2, 3), (3, 4)]);
+
+        let before = graph.closure(&[4, 3, 1, 2]);
+        assert!(before[2][0] && before[2][1] && before[3][0]);
+        assert!(!before[0][2] && !before[2][2]);
+
+        // Without 3 there is no way from 2 to 4.
+        assert!(!graph.closure(&[4, 1, 2])[2][0]);
+        assert_eq!(graph.sort_keeping(&[4, 1, 2], &[2, 4]), Ok(vec![1, 2, 4]));
+    }
+}
@@ -0,0 +1,279 @@
+use graph::{Cycle, RuleGraph};
+use harness::{parse_number, ParseError, Solution};
+use order::Consistency;
+use repair::{Repair, Violation};
+use std::{error::Error, fmt::Display, str::FromStr};
+
+pub mod graph;
+pub mod order;
+pub mod repair;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    rules: Vec<Rule>,
+    graph: RuleGraph,
+    updates: Vec<Update>,
+}
+
+// Page `left` has to be printed before page `right`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub left: usize,
+    pub right: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Update {
+    pub pages: Vec<usize>,
+}
+
+impl Problem {
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn graph(&self) -> &RuleGraph {
+        &self.graph
+    }
+
+    pub fn updates(&self) -> &[Update] {
+        &self.updates
+    }
+
+    pub fn valid_updates(&self) -> Vec<&Update> {
+        self.updates
+            .iter()
+            .filter(|&update| self.verify_update(update))
+            .collect()
+    }
+
+    pub fn invalid_updates(&self) -> Vec<&Update> {
+        self.updates
+            .iter()
+            .filter(|&update| !self.verify_update(update))
+            .collect()
+    }
+
+    pub fn verify_update(&self, update: &Update) -> bool {
+        update
+            .pages
+            .windows(2)
+            .all(|window| self.graph.has_rule(window[0], window[1]))
+    }
+
+    // A looser check than `verify_update`: neighbours don't need a rule of their own, as long as the update doesn't go
+    // against the rules through the other pages. See the order module.
+    pub fn check(&self, update: &Update) -> Consistency {
+        order::check(&self.graph, update)
+    }
+
+    // So I had this epiphany: the set of rules are a directed acyclic graph. Therfore, if we take the subgraph
+    // containing only nodes that are part of the update, the in-degree equals the index in the corrected update list.
+    // Neat, but nothing checked that the rules really were acyclic, and when they're not the trick happily returns a
+    // wrong order. Now it's a proper topological sort that tells us about the cycle instead.
+    pub fn correct_update(&self, update: &Update) -> Result<Update, Cycle> {
+        let pages = self.graph.sort(&update.pages)?;
+        Ok(Update { pages })
+    }
+
+    // Every rule the update breaks, not just the ones between neighbours.
+    pub fn violations(&self, update: &Update) -> Vec<Violation> {
+        repair::violations(&self.graph, update)
+    }
+
+    // The fewest pages to move to get a valid update, and the update after moving them.
+    pub fn repair(&self, update: &Update) -> Result<Repair, Cycle> {
+        repair::repair(&self.graph, update)
+    }
+}
+
+impl Update {
+    pub fn middle(&self) -> usize {
+        self.pages[self.pages.len() / 2]
+    }
+}
+
+// This used to be a neat little trick: a generic From<S> implementation for anything that implements AsRef<str>, so it
+// didn't matter if I had a String, &String or &str. That trick doesn't work for TryFrom, because the standard library
+// already implements TryFrom for every type that implements From and the compiler can't rule out the two overlapping.
+// FromStr is the way to go for parsing anyway, and with `.parse()` it works on all of those string types too.
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (left, right) = line
+            .split_once('|')
+            .ok_or_else(|| ParseError::in_line(line, line, "expected a rule like 47|53"))?;
+        Ok(Self {
+            left: parse_number(line, left)?,
+            right: parse_number(line, right)?,
+        })
+    }
+}
+
+// A page can only be printed once per update. Everything that puts pages in order goes by page number, so a page that's
+// in there twice would quietly get lost along the way.
+impl FromStr for Update {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut pages = Vec::new();
+        for part in line.split(',') {
+            let page = parse_number(line, part)?;
+            if pages.contains(&page) {
+                return Err(ParseError::in_line(
+                    line,
+                    part,
+                    "this page is already in the update",
+                ));
+            }
+            pages.push(page);
+        }
+        Ok(Self { pages })
+    }
+}
+
+// The rules and the updates are separated by an empty line. We walk the lines ourselves instead of splitting on the
+// empty line, so we know on which line of the input a parse error is.
+impl FromStr for Problem {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lines = input.lines().enumerate();
+
+        let rules = lines
+            .by_ref()
+            .take_while(|(_, line)| !line.is_empty())
+            .map(|(index, line)| line.parse().map_err(|e: ParseError| e.on_line(index + 1)))
+            .collect::<Result<Vec<Rule>, _>>()?;
+        let graph = RuleGraph::new(&rules);
+
+        let updates = lines
+            .map(|(index, line)| line.parse().map_err(|e: ParseError| e.on_line(index + 1)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            rules,
+            graph,
+            updates,
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Problem, ParseError> {
+    input.parse()
+}
+
+pub fn part_a(problem: &Problem) -> usize {
+    problem
+        .valid_updates()
+        .iter()
+        .map(|update| update.middle())
+        .sum()
+}
+
+// Only part B needs to put updates in order, so this is where the rules of an update can turn out to go round in
+// circles. Then there's no right order and no answer.
+pub fn part_b(problem: &Problem) -> Result<usize, Cycle> {
+    problem
+        .invalid_updates()
+        .iter()
+        .map(|update| Ok(problem.correct_update(update)?.middle()))
+        .sum()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Problem;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(problem: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(problem))
+    }
+
+    fn part_b(problem: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        part_b(problem)
+            .map_err(|cycle| format!("no valid order, the rules have a cycle: {}", cycle).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day05-1.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day05>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_a, "143");
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day05>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_b, "123");
+    }
+
+    #[test]
+    fn repairs_example() {
+        let problem = parse(EXAMPLE).unwrap();
+
+        let moves: Vec<usize> = problem
+            .updates()
+            .iter()
+            .map(|update| problem.repair(update).unwrap().moves.len())
+            .collect();
+        assert_eq!(moves, vec![0, 0, 0, 1, 1, 2]);
+
+        for update in problem.invalid_updates() {
+            assert!(!problem.violations(update).is_empty());
+            assert_eq!(
+                problem.repair(update).unwrap().update,
+                problem.correct_update(update).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn check_example() {
+        let problem = parse(EXAMPLE).unwrap();
+
+        // The rules of the example fix the order of every update, so the valid ones are consistent.
+        for update in problem.updates() {
+            let consistent = problem.check(update) == Consistency::Consistent;
+            assert_eq!(consistent, problem.verify_update(update));
+        }
+    }
+
+    #[test]
+    fn pages_are_printed_once() {
+        let error = parse("1|2\n2|3\n\n3,2,3,1\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 5: this page is already in the update (found \"3\")"
+        );
+    }
+
+    #[test]
+    fn cycles_only_matter_for_part_b() {
+        let input = "1|2\n2|3\n3|1\n\n1,2\n3,2,1\n";
+        let problem = parse(input).unwrap();
+
+        assert_eq!(part_a(&problem), 2);
+        assert_eq!(
+            part_b(&problem).unwrap_err().to_string(),
+            "1 -> 2 -> 3 -> 1"
+        );
+
+        let error = harness::solve::<Day05>(input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "no valid order, the rules have a cycle: 1 -> 2 -> 3 -> 1"
+        );
+    }
+}
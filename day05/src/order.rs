@@ -0,0 +1,205 @@
+use crate::{graph::RuleGraph, Update};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+// A looser check than `Problem::verify_update`, which wants a rule between every two neighbours. Here an update is fine
+// as long as it doesn't go against the rules, also not through other pages of the update (see `RuleGraph::closure`).
+// That leaves three possibilities:
+//
+// - consistent: the rules fix the order of every pair of pages, and the update has them in that order
+// - violated: some page comes after a page it has to be before
+// - underdetermined: nothing is wrong, but some pages could just as well be the other way around, so the update is only
+//   one of several valid orders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Consistency {
+    Consistent,
+    // The positions of every pair in the wrong order: the page at `.1` has to come before the page at `.0`.
+    Violated { pairs: Vec<(usize, usize)> },
+    // How many valid orders there are, this update being one of them. None when there are too many to count, see
+    // `count_orders`.
+    Underdetermined { orders: Option<u128> },
+}
+
+pub fn check(graph: &RuleGraph, update: &Update) -> Consistency {
+    let n = update.pages.len();
+    let before = graph.closure(&update.pages);
+
+    let pairs: Vec<(usize, usize)> = (0..n)
+        .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+        .filter(|&(i, j)| before[j][i])
+        .collect();
+    if !pairs.is_empty() {
+        return Consistency::Violated { pairs };
+    }
+
+    let determined = (0..n).all(|i| (i + 1..n).all(|j| before[i][j]));
+    if determined {
+        Consistency::Consistent
+    } else {
+        Consistency::Underdetermined {
+            orders: count_orders(&before),
+        }
+    }
+}
+
+// Count the orders that don't break any rule, the linear extensions. Every order is built up front to back, and what
+// matters for the rest of the order is only which pages have been placed already, not in which order. So we count per
+// set of placed pages, and there are only as many of those as there are ways to cut the rules in a before and after
+// part. For updates that are nearly fixed that's a handful, but when hardly any rules apply it's closer to 2^n (and
+// the count itself closer to n!). So we give up after `MAX_PLACED` sets, or when the count doesn't fit in a u128.
+const MAX_PLACED: usize = 1 << 14;
+
+// A set of pages of an update, one bit per position, in as many words as it takes.
+type Pages = Vec<u64>;
+
+fn contains(pages: &Pages, page: usize) -> bool {
+    pages[page / 64] & 1 << (page % 64) != 0
+}
+
+fn with(pages: &Pages, page: usize) -> Pages {
+    let mut pages = pages.clone();
+    pages[page / 64] |= 1 << (page % 64);
+    pages
+}
+
+fn count_orders(before: &[Vec<bool>]) -> Option<u128> {
+    let n = before.len();
+    let empty: Pages = vec![0; n.div_ceil(64)];
+
+    // The pages that have to come before each page.
+    let required: Vec<Pages> = (0..n)
+        .map(|page| {
+            (0..n)
+                .filter(|&other| before[other][page])
+                .fold(empty.clone(), |set, other| with(&set, other))
+        })
+        .collect();
+
+    fn count(placed: Pages, required: &[Pages], memo: &mut HashMap<Pages, u128>) -> Option<u128> {
+        if let Some(&orders) = memo.get(&placed) {
+            return Some(orders);
+        }
+        let ready: Vec<usize> = (0..required.len())
+            .filter(|&page| {
+                !contains(&placed, page)
+                    && required[page]
+                        .iter()
+                        .zip(&placed)
+                        .all(|(required, placed)| required & !placed == 0)
+            })
+            .collect();
+        if ready.is_empty() {
+            // Every page is placed. Nothing can be stuck here, a cycle would have been a violation.
+            return Some(1);
+        }
+        if memo.len() >= MAX_PLACED {
+            return None;
+        }
+
+        let mut orders: u128 = 0;
+        for page in ready {
+            orders = orders.checked_add(count(with(&placed, page), required, memo)?)?;
+        }
+        memo.insert(placed, orders);
+        Some(orders)
+    }
+
+    count(empty, &required, &mut HashMap::new())
+}
+
+impl Display for Consistency {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Consistency::Consistent => write!(f, "consistent"),
+            Consistency::Violated { pairs } if pairs.len() == 1 => {
+                write!(f, "violated (1 pair in the wrong order)")
+            }
+            Consistency::Violated { pairs } => {
+                write!(f, "violated ({} pairs in the wrong order)", pairs.len())
+            }
+            Consistency::Underdetermined {
+                orders: Some(orders),
+            } => write!(f, "underdetermined ({} valid orders)", orders),
+            Consistency::Underdetermined { orders: None } => {
+                write!(f, "underdetermined (too many valid orders to count)")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_with(rules: &[(usize, usize)], pages: &[usize]) -> Consistency {
+        let update = Update {
+            pages: pages.to_vec(),
+        };
+        check(&RuleGraph::from_pairs(rules), &update)
+    }
+
+    #[test]
+    fn neighbours_without_a_rule() {
+        // There is no rule between 1 and 3, but 2 puts them in order.
+        let rules = [(1, 2), (2, 3)];
+
+        assert_eq!(check_with(&rules, &[1, 2, 3]), Consistency::Consistent);
+        assert_eq!(
+            check_with(&rules, &[3, 1, 2]),
+            Consistency::Violated {
+                pairs: vec![(0, 1), (0, 2)]
+            }
+        );
+    }
+
+    #[test]
+    fn orders_are_counted() {
+        // 1 first, then 2 and 3 in any order, then 4.
+        let rules = [(1, 2), (1, 3), (2, 4), (3, 4)];
+        assert_eq!(
+            check_with(&rules, &[1, 3, 2, 4]),
+            Consistency::Underdetermined { orders: Some(2) }
+        );
+
+        // Without any rules every order is fine.
+        assert_eq!(
+            check_with(&[], &[1, 2, 3, 4, 5]),
+            Consistency::Underdetermined { orders: Some(120) }
+        );
+    }
+
+    #[test]
+    fn long_updates() {
+        // A hundred pages in a fixed order, except that 1 and 2 can go either way.
+        let pages: Vec<usize> = (1..=100).collect();
+        let mut rules: Vec<(usize, usize)> = (2..100).map(|page| (page, page + 1)).collect();
+        rules.push((1, 3));
+        assert_eq!(
+            check_with(&rules, &pages),
+            Consistency::Underdetermined { orders: Some(2) }
+        );
+
+        // 65 pages and a single rule: way more orders than fit in a u128.
+        let pages: Vec<usize> = (1..=65).collect();
+        assert_eq!(
+            check_with(&[(1, 2)], &pages),
+            Consistency::Underdetermined { orders: None }
+        );
+    }
+
+    #[test]
+    fn too_many_orders_to_count() {
+        // 30! fits in a u128, but counting it would take 2^30 sets of placed pages.
+        let pages: Vec<usize> = (1..=30).collect();
+        assert_eq!(
+            check_with(&[], &pages),
+            Consistency::Underdetermined { orders: None }
+        );
+        assert_eq!(
+            check_with(&[], &pages).to_string(),
+            "underdetermined (too many valid orders to count)"
+        );
+    }
+}
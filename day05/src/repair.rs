@@ -0,0 +1,234 @@
+use crate::{
+    graph::{Cycle, RuleGraph},
+    Rule, Update,
+};
+use std::fmt::{Display, Formatter};
+
+// Why an update is wrong and how to fix it with as little work as possible. Checking only neighbours is enough to tell
+// if an update is right (every rule we need is between neighbours in the right order), but it doesn't tell you what's
+// wrong with it. So here we look at every pair of pages.
+//
+// The fix is moving pages around. The pages that don't move have to be in an order that can stay: no page among them
+// may come after a page it has to be before, also not through the other pages of the update. Every other page gets
+// picked up and put back where it belongs, so the fewer pages we have to pick up the better. Which is the largest set
+// of pages that can stay? Call a pair of pages crossed when they're in the wrong order, directly or through other
+// pages. Being crossed is transitive: if a has to go after b and b after c, then a has to go after c. So the crossed
+// pairs form a partial order, and the pages that can stay are exactly the ones of which no two are crossed, an
+// antichain. Dilworth tells us how to find the largest one: with a maximum matching.
+
+// A rule that the update breaks: `rule.left` has to be printed before `rule.right`, but it's at `positions.1`, after
+// `rule.right` at `positions.0`. Positions start at zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub rule: Rule,
+    pub positions: (usize, usize),
+}
+
+// Pick up a page and put it somewhere else. The positions are in the update before and after the repair, so the moves
+// work as if you first pick up all pages that move and then put them back in the order of the moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub page: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+    pub moves: Vec<Move>,
+    pub update: Update,
+}
+
+pub fn violations(graph: &RuleGraph, update: &Update) -> Vec<Violation> {
+    let pages = &update.pages;
+    let mut violations = Vec::new();
+    for i in 0..pages.len() {
+        for j in i + 1..pages.len() {
+            if graph.has_rule(pages[j], pages[i]) {
+                violations.push(Violation {
+                    rule: Rule {
+                        left: pages[j],
+                        right: pages[i],
+                    },
+                    positions: (i, j),
+                });
+            }
+        }
+    }
+    violations
+}
+
+pub fn repair(graph: &RuleGraph, update: &Update) -> Result<Repair, Cycle> {
+    // Without a valid order there is nothing to repair towards.
+    graph.sort(&update.pages)?;
+
+    let pages = &update.pages;
+    let keep: Vec<usize> = stay(graph, pages)
+        .into_iter()
+        .map(|index| pages[index])
+        .collect();
+    let sorted = graph
+        .sort_keeping(pages, &keep)
+        .expect("the pages that stay don't break any rules");
+
+    let mut moves: Vec<Move> = pages
+        .iter()
+        .enumerate()
+        .filter(|(_, page)| !keep.contains(page))
+        .map(|(from, &page)| Move {
+            page,
+            from,
+            to: sorted.iter().position(|&p| p == page).unwrap(),
+        })
+        .collect();
+    moves.sort_by_key(|step| step.to);
+
+    Ok(Repair {
+        moves,
+        update: Update { pages: sorted },
+    })
+}
+
+// The positions of the largest set of pages that can stay where they are, see the top of this file. Position `a` and
+// `b > a` are crossed when the page at `b` has to come before the page at `a`. A maximum matching between crossed pairs
+// (Kuhn's algorithm) gives a smallest cover of the pages with chains of crossed pages, and König's theorem turns that
+// into a largest antichain: the pages we can reach from an unmatched page on the left side of the matching, but not on
+// the right side.
+fn stay(graph: &RuleGraph, pages: &[usize]) -> Vec<usize> {
+    let n = pages.len();
+    let before = graph.closure(pages);
+    let crossed = |a: usize, b: usize| a < b && before[b][a];
+
+    // matched[b] is the page on the left that is matched to `b` on the right.
+    let mut matched: Vec<Option<usize>> = vec![None; n];
+    for a in 0..n {
+        let mut seen = vec![false; n];
+        augment(a, &crossed, &mut matched, &mut seen);
+    }
+
+    let mut left = vec![false; n];
+    let mut right = vec![false; n];
+    let mut todo: Vec<usize> = (0..n).filter(|&a| !matched.contains(&Some(a))).collect();
+    while let Some(a) = todo.pop() {
+        if left[a] {
+            continue;
+        }
+        left[a] = true;
+        for b in 0..n {
+            if crossed(a, b) && !right[b] {
+                right[b] = true;
+                if let Some(next) = matched[b] {
+                    todo.push(next);
+                }
+            }
+        }
+    }
+
+    (0..n).filter(|&x| left[x] && !right[x]).collect()
+}
+
+fn augment(
+    a: usize,
+    crossed: &impl Fn(usize, usize) -> bool,
+    matched: &mut [Option<usize>],
+    seen: &mut [bool],
+) -> bool {
+    for b in 0..matched.len() {
+        if crossed(a, b) && !seen[b] {
+            seen[b] = true;
+            if matched[b].is_none_or(|other| augment(other, crossed, matched, seen)) {
+                matched[b] = Some(a);
+                return true;
+            }
+        }
+    }
+    false
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{} is broken: {} is at {}, after {} at {}",
+            self.rule.left,
+            self.rule.right,
+            self.rule.left,
+            self.positions.1 + 1,
+            self.rule.right,
+            self.positions.0 + 1
+        )
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.page,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(pages: &[usize]) -> Update {
+        Update {
+            pages: pages.to_vec(),
+        }
+    }
+
+    #[test]
+    fn every_broken_rule() {
+        let rules = RuleGraph::from_pairs(&[(1, 2), (2, 3), (1, 3)]);
+
+        let broken = violations(&rules, &update(&[3, 2, 1]));
+        assert_eq!(broken.len(), 3);
+        assert_eq!(
+            broken[1].to_string(),
+            "1|3 is broken: 1 is at 3, after 3 at 1"
+        );
+
+        assert!(violations(&rules, &update(&[1, 2, 3])).is_empty());
+    }
+
+    #[test]
+    fn fewest_moves() {
+        let rules = RuleGraph::from_pairs(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+
+        // Only 5 is in the wrong place.
+        let fixed = repair(&rules, &update(&[5, 1, 2, 3, 4])).unwrap();
+        assert_eq!(
+            fixed.moves,
+            vec![Move {
+                page: 5,
+                from: 0,
+                to: 4
+            }]
+        );
+        assert_eq!(fixed.update, update(&[1, 2, 3, 4, 5]));
+
+        // Everything is reversed, all but one page have to move.
+        let fixed = repair(&rules, &update(&[5, 4, 3, 2, 1])).unwrap();
+        assert_eq!(fixed.moves.len(), 4);
+    }
+
+    #[test]
+    fn fewest_moves_without_a_total_order() {
+        // 3 has to go before 1, but 2 doesn't care where it is. So one of 1 and 3 moves and the other two stay.
+        let rules = RuleGraph::from_pairs(&[(3, 1)]);
+        let fixed = repair(&rules, &update(&[2, 1, 3])).unwrap();
+        assert_eq!(fixed.moves.len(), 1);
+        assert!(violations(&rules, &fixed.update).is_empty());
+
+        // Through 4, 3 has to go before 1 here too.
+        let rules = RuleGraph::from_pairs(&[(3, 4), (4, 1)]);
+        let fixed = repair(&rules, &update(&[1, 2, 4, 3])).unwrap();
+        assert_eq!(fixed.moves.len(), 2);
+        assert!(violations(&rules, &fixed.update).is_empty());
+    }
+}
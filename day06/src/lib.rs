@@ -0,0 +1,354 @@
+use grid::{Coordinate, Direction, Grid};
+use harness::{ParseError, Solution};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+const GUARD_START: u8 = b'^';
+const OBSTACLE: u8 = b'#';
+const EMPTY: u8 = b'.';
+const VISITED_UP_DOWN: u8 = b'|';
+const VISITED_LEFT_RIGHT: u8 = b'-';
+const VISITED_UP_DOWN_LEFT_RIGHT: u8 = b'+';
+const PADDING: u8 = b'@';
+
+// Where the guard is and where it's looking. This is all there is to the state of a walk: the lab itself doesn't change
+// while the guard walks (the marks are only for us), so a guard that is in the same place looking the same way as
+// before will do exactly what it did before, forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Guard {
+    pub position: Coordinate,
+    pub direction: Direction,
+}
+
+// How a walk ends. Steps count both moving forward and turning. A loop starts at the step where the guard was first in
+// the state it keeps coming back to, and takes `cycle_len` steps to get back there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Exited {
+        steps: usize,
+    },
+    Loop {
+        cycle_start: usize,
+        cycle_len: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Lab {
+    grid: Grid<u8>,
+    guard: Guard,
+    steps: usize,
+    // Every state of the guard so far, in order, and at which step it was first seen.
+    path: Vec<Guard>,
+    seen: HashMap<Guard, usize>,
+    outcome: Option<Outcome>,
+}
+
+impl Lab {
+    // Look at whats in front of the guard
+    fn look(&self) -> u8 {
+        self.grid[self.guard.position.step(self.guard.direction)]
+    }
+
+    // Move the guard in the direction it is facing and update the grid
+    fn move_guard(&mut self) {
+        self.guard.position = self.guard.position.step(self.guard.direction);
+
+        let cell = &mut self.grid[self.guard.position];
+        if *cell == EMPTY {
+            *cell = match self.guard.direction {
+                Direction::Up | Direction::Down => VISITED_UP_DOWN,
+                _ => VISITED_LEFT_RIGHT,
+            };
+        } else {
+            *cell = VISITED_UP_DOWN_LEFT_RIGHT;
+        }
+    }
+
+    // Rotate the guard 90 degrees clockwise
+    fn rotate_guard(&mut self) {
+        self.guard.direction = self.guard.direction.turn_right();
+    }
+
+    // Perform the next step for the guard
+    pub fn step(&mut self) {
+        if self.is_done() {
+            return;
+        }
+
+        match self.look() {
+            VISITED_UP_DOWN | VISITED_LEFT_RIGHT | VISITED_UP_DOWN_LEFT_RIGHT | EMPTY => {
+                self.move_guard();
+            }
+            OBSTACLE => {
+                self.rotate_guard();
+            }
+            PADDING => {
+                self.steps += 1;
+                self.outcome = Some(Outcome::Exited { steps: self.steps });
+                return;
+            }
+            _ => panic!("a cannae step"),
+        }
+        self.steps += 1;
+
+        // Been here before, looking the same way? Then we're going round in circles.
+        match self.seen.get(&self.guard) {
+            Some(&cycle_start) => {
+                self.outcome = Some(Outcome::Loop {
+                    cycle_start,
+                    cycle_len: self.steps - cycle_start,
+                })
+            }
+            None => {
+                self.seen.insert(self.guard, self.steps);
+                self.path.push(self.guard);
+            }
+        }
+    }
+
+    // Let the guard walk until it leaves the lab or walks in circles.
+    pub fn run(&mut self) -> Outcome {
+        while !self.is_done() {
+            self.step();
+        }
+        self.outcome.unwrap()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    // Every state the guard has been in so far, the state at step n is at index n.
+    pub fn path(&self) -> &[Guard] {
+        &self.path
+    }
+
+    // The states the guard keeps going through, once we know it's walking in circles.
+    pub fn loop_path(&self) -> Option<&[Guard]> {
+        match self.outcome? {
+            Outcome::Loop {
+                cycle_start,
+                cycle_len,
+            } => Some(&self.path[cycle_start..cycle_start + cycle_len]),
+            Outcome::Exited { .. } => None,
+        }
+    }
+
+    fn is_visited(c: u8) -> bool {
+        c == VISITED_UP_DOWN || c == VISITED_LEFT_RIGHT || c == VISITED_UP_DOWN_LEFT_RIGHT
+    }
+
+    pub fn sum_visited(&self) -> usize {
+        self.grid.positions(|&c| Self::is_visited(c)).count()
+    }
+
+    // Ok, so this is the even uglier part. We're running a brute force algorithm to find the loops by adding an
+    // obstruction to the grid at each point and then checking if the guard loops. This used to run the guard for "a
+    // while" (twice the number of cells) and call it a loop when it hadn't left by then. Now we check if the guard has
+    // been in the same place looking the same way before, which is a loop for sure.
+    //
+    // Another thing is that we're not checking all the fields, it only makes sense to test with a field where the guard
+    // actually passes in the part A grid. So we just pass the part A grid and take only the visited fields into
+    // concideration.
+    fn find_loops(&self, part_a_lab: Lab) -> usize {
+        self.grid
+            .coordinates()
+            .filter(|&coordinate| {
+                self.grid[coordinate] == EMPTY && Self::is_visited(part_a_lab.grid[coordinate])
+            })
+            .filter(|&coordinate| !self.exits_with_obstruction(coordinate))
+            .count()
+    }
+
+    // This used to clone the whole lab for every obstruction we tried, which meant most of part B was spent copying
+    // grids around. Now the obstruction goes into an overlay on top of the untouched grid and the guard doesn't mark
+    // where it has been, so nothing gets copied at all.
+    //
+    // We only need to know if the guard gets out, not how, so we only remember the states where the guard turns. Every
+    // loop has a turn in it (walking straight on gets you out of the lab), so that's enough to notice it, and it's a lot
+    // less to remember than every single step.
+    fn exits_with_obstruction(&self, obstruction: Coordinate) -> bool {
+        let mut grid = self.grid.overlay();
+        grid.set(obstruction, OBSTACLE);
+
+        let mut guard = self.guard;
+        let mut turns = HashSet::new();
+        loop {
+            let ahead = guard.position.step(guard.direction);
+            match grid[ahead] {
+                OBSTACLE => {
+                    if !turns.insert(guard) {
+                        return false;
+                    }
+                    guard.direction = guard.direction.turn_right()
+                }
+                PADDING => return true,
+                _ => guard.position = ahead,
+            }
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Outcome::Exited { steps } => write!(f, "the guard left the lab after {} steps", steps),
+            Outcome::Loop {
+                cycle_start,
+                cycle_len,
+            } => write!(
+                f,
+                "the guard walks in circles from step {}, every {} steps",
+                cycle_start, cycle_len
+            ),
+        }
+    }
+}
+
+// The Dispaly trait allows the grid to be printed in a regular {} block.
+impl Display for Lab {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+// We use the shared padded grid so we can look without bounds check. Yes, I hate doing bounds checks. Anything else than
+// an empty field, an obstacle or the guard would make the guard panic later on, so we'd better catch that right here. The
+// same goes for a second guard: ours would walk right into it.
+impl FromStr for Lab {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let is_valid = |c: char| c.is_ascii() && matches!(c as u8, EMPTY | OBSTACLE | GUARD_START);
+
+        let mut guards = 0;
+        for (index, line) in input.lines().enumerate() {
+            if let Some((col, c)) = line.char_indices().find(|&(_, c)| !is_valid(c)) {
+                let part = &line[col..col + c.len_utf8()];
+                let error = ParseError::in_line(line, part, "expected ., # or ^");
+                return Err(error.on_line(index + 1));
+            }
+            for (col, _) in line.match_indices(GUARD_START as char) {
+                guards += 1;
+                if guards > 1 {
+                    let error =
+                        ParseError::in_line(line, &line[col..col + 1], "there is already a guard");
+                    return Err(error.on_line(index + 1));
+                }
+            }
+        }
+
+        let mut grid = Grid::parse(input, 1, PADDING);
+
+        let position = grid
+            .find(&GUARD_START)
+            .ok_or_else(|| ParseError::new(1, "", "there is no guard (^) in the lab"))?;
+        let guard = Guard {
+            position,
+            direction: Direction::Up,
+        };
+        grid[position] = VISITED_UP_DOWN;
+
+        Ok(Self {
+            grid,
+            guard,
+            steps: 0,
+            path: vec![guard],
+            seen: HashMap::from([(guard, 0)]),
+            outcome: None,
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Lab, ParseError> {
+    input.parse()
+}
+
+pub fn part_a(lab: &Lab) -> usize {
+    let mut part_a_lab = lab.clone();
+    part_a_lab.run();
+    part_a_lab.sum_visited()
+}
+
+// Part B needs the walked part A grid to know where to put obstacles, so we simply walk it again.
+pub fn part_b(lab: &Lab) -> usize {
+    let mut part_a_lab = lab.clone();
+    part_a_lab.run();
+    lab.find_loops(part_a_lab)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Lab;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(lab: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(lab))
+    }
+
+    fn part_b(lab: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_b(lab))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day06-1.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day06>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_a, "41");
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day06>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_b, "6");
+    }
+
+    #[test]
+    fn one_guard_only() {
+        let error = parse("#..\n^.^\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: there is already a guard (found \"^\")"
+        );
+    }
+
+    #[test]
+    fn outcomes() {
+        // Straight up and out: four steps up, one out.
+        let mut lab = parse(".\n.\n.\n.\n^\n").unwrap();
+        assert_eq!(lab.run(), Outcome::Exited { steps: 5 });
+        assert_eq!(lab.loop_path(), None);
+
+        // One step up, then round and round: step up, turn, step right, turn, step down, turn, step left, turn.
+        let mut lab = parse(".#..\n...#\n#...\n.^#.\n").unwrap();
+        assert_eq!(
+            lab.run(),
+            Outcome::Loop {
+                cycle_start: 1,
+                cycle_len: 8
+            }
+        );
+        let path = lab.loop_path().unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!(path[0], lab.path()[1]);
+    }
+}
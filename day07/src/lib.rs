@@ -0,0 +1,159 @@
+use harness::{parse_number, ParseError, Solution};
+use itertools::{repeat_n, Itertools};
+use std::{error::Error, fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Multiply,
+    Concatenate,
+}
+
+// An equation always has at least one value, parsing makes sure of that.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    result: usize,
+    values: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    equations: Vec<Equation>,
+}
+
+impl Equation {
+    pub fn result(&self) -> usize {
+        self.result
+    }
+
+    pub fn values(&self) -> &[usize] {
+        &self.values
+    }
+
+    // We could optimize this further by halting early if the intermediate results is larger than the result we are
+    // looking for.
+    fn test_with_operators(&self, operators: Vec<&Operator>) -> bool {
+        let result = self.values.iter().skip(1).zip(operators.iter()).fold(
+            self.values[0],
+            |result, (value, operator)| match operator {
+                Operator::Add => result + value,
+                Operator::Multiply => result * value,
+                Operator::Concatenate => {
+                    let mut result = result.to_string();
+                    result.push_str(&value.to_string());
+                    result.parse().unwrap()
+                }
+            },
+        );
+
+        result == self.result
+    }
+
+    // The bulk of work is done by the itertools crate that has the repeat_n and multi_cartesian_product functions.
+    // Together these create all possible combinations of operators of length n from a set of options. We simply test
+    // each possiblility to see if it is a valid result.
+    pub fn has_valid_result(&self, operators: &[Operator]) -> bool {
+        repeat_n(operators.iter(), self.values.len() - 1)
+            .multi_cartesian_product()
+            .any(|operators| self.test_with_operators(operators))
+    }
+}
+
+impl Problem {
+    pub fn equations(&self) -> &[Equation] {
+        &self.equations
+    }
+
+    pub fn sum_valid_results(&self, operators: &[Operator]) -> usize {
+        self.equations
+            .iter()
+            .filter(|equation| equation.has_valid_result(operators))
+            .map(|equation| equation.result)
+            .sum()
+    }
+}
+
+impl FromStr for Problem {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let equations = input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| line.parse().map_err(|e: ParseError| e.on_line(index + 1)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Problem { equations })
+    }
+}
+
+impl FromStr for Equation {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (result, values) = line.split_once(':').ok_or_else(|| {
+            ParseError::in_line(line, line, "expected an equation like 190: 10 19")
+        })?;
+
+        let result = parse_number(line, result)?;
+        let values: Vec<usize> = values
+            .split_whitespace()
+            .map(|value| parse_number(line, value))
+            .collect::<Result<_, _>>()?;
+
+        if values.is_empty() {
+            return Err(ParseError::at_end(line, "expected at least one value"));
+        }
+
+        Ok(Equation { result, values })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Problem, ParseError> {
+    input.parse()
+}
+
+pub fn part_a(problem: &Problem) -> usize {
+    problem.sum_valid_results(&[Operator::Add, Operator::Multiply])
+}
+
+pub fn part_b(problem: &Problem) -> usize {
+    problem.sum_valid_results(&[Operator::Add, Operator::Multiply, Operator::Concatenate])
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Problem;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(problem: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(problem))
+    }
+
+    fn part_b(problem: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_b(problem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day07-1.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day07>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_a, "3749");
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day07>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_b, "11387");
+    }
+}
@@ -0,0 +1,190 @@
+use harness::{ParseError, Solution};
+use itertools::Itertools;
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Coordinates {
+    pub x: isize,
+    pub y: isize,
+}
+#[derive(Debug)]
+pub struct Antenna {
+    pub coordinates: Coordinates,
+}
+
+// All antennas with the same frequency.
+#[derive(Debug)]
+pub struct AntennaGroup {
+    pub antennas: Vec<Antenna>,
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    antenna_groups: HashMap<char, AntennaGroup>,
+    width: isize,
+    height: isize,
+}
+
+impl AntennaGroup {
+    pub fn calculate_antinodes(&self, resonance: bool) -> Vec<Coordinates> {
+        self.antennas
+            .iter()
+            .enumerate()
+            .flat_map(|(a, antenna)| {
+                self.antennas
+                    .iter()
+                    .enumerate()
+                    .map(move |(b, other_antenna)| {
+                        if a == b {
+                            return vec![];
+                        }
+
+                        let dx = antenna.coordinates.x - other_antenna.coordinates.x;
+                        let dy = antenna.coordinates.y - other_antenna.coordinates.y;
+
+                        match resonance {
+                            false => {
+                                // For part A, just return the first antinode.
+                                vec![Coordinates {
+                                    x: other_antenna.coordinates.x - dx,
+                                    y: other_antenna.coordinates.y - dy,
+                                }]
+                            }
+                            true => {
+                                // Yeah yeah yeah, it's ugly but it works. We could do something clever with the width
+                                // and height of the grid, but I'll admit I'm just lazy. It's sunday after all.
+                                (0..50)
+                                    .map(|i| Coordinates {
+                                        x: other_antenna.coordinates.x - (i * dx),
+                                        y: other_antenna.coordinates.y - (i * dy),
+                                    })
+                                    .collect()
+                            }
+                        }
+                    })
+            })
+            // Did I tell you I was lazy yet?
+            .flatten()
+            .collect()
+    }
+}
+
+impl Problem {
+    pub fn antenna_groups(&self) -> &HashMap<char, AntennaGroup> {
+        &self.antenna_groups
+    }
+
+    pub fn width(&self) -> isize {
+        self.width
+    }
+
+    pub fn height(&self) -> isize {
+        self.height
+    }
+
+    pub fn count_all_antinodes(&self, resonance: bool) -> usize {
+        self.antenna_groups
+            .values()
+            .map(|group| AntennaGroup::calculate_antinodes(group, resonance))
+            .flat_map(|v| {
+                v.into_iter()
+                    .filter(|coordinates| {
+                        // But Derk, I though you hated bounds checking?
+                        coordinates.x >= 0
+                            && coordinates.x < self.width
+                            && coordinates.y >= 0
+                            && coordinates.y < self.height
+                    })
+                    .collect::<Vec<Coordinates>>()
+            })
+            // Lazy again
+            .unique()
+            .count()
+    }
+}
+
+impl FromStr for Problem {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut antenna_groups = HashMap::new();
+        input.lines().enumerate().for_each(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_x, c)| *c != '.')
+                .for_each(|(x, c)| {
+                    let entry = antenna_groups.entry(c).or_insert(AntennaGroup {
+                        antennas: Vec::new(),
+                    });
+                    entry.antennas.push(Antenna {
+                        coordinates: Coordinates {
+                            x: x as isize,
+                            y: y as isize,
+                        },
+                    });
+                });
+        });
+
+        let height = input.lines().count() as isize;
+        let width = input
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::new(1, "", "the map is empty"))?
+            .len() as isize;
+
+        Ok(Problem {
+            antenna_groups,
+            width,
+            height,
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Problem, ParseError> {
+    input.parse()
+}
+
+pub fn part_a(problem: &Problem) -> usize {
+    problem.count_all_antinodes(false)
+}
+
+pub fn part_b(problem: &Problem) -> usize {
+    problem.count_all_antinodes(true)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Problem;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(problem: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(problem))
+    }
+
+    fn part_b(problem: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_b(problem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day08-1.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day08>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_a, "14");
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day08>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_b, "34");
+    }
+}
@@ -0,0 +1,215 @@
+use harness::{ParseError, Solution};
+use std::{error::Error, fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Block {
+    Empty,
+    Used(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct DiskMap {
+    blocks: Vec<Block>,
+}
+
+// The puzzle input is the dense format of the disk map, so that's what we parse. The trailing newline is not part of
+// it, everything else should be a digit.
+impl FromStr for DiskMap {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let dense = input.trim_end();
+
+        let mut blocks = Vec::new();
+        for (i, (offset, c)) in dense.char_indices().enumerate() {
+            let length = c.to_digit(10).ok_or_else(|| {
+                ParseError::in_line(
+                    dense,
+                    &dense[offset..offset + c.len_utf8()],
+                    "expected a digit",
+                )
+            })?;
+            (0..length).for_each(|_c| {
+                blocks.push(if i % 2 == 0 {
+                    Block::Used(i / 2)
+                } else {
+                    Block::Empty
+                });
+            });
+        }
+
+        if !blocks.iter().any(|block| matches!(block, Block::Used(_))) {
+            return Err(ParseError::new(1, dense, "the disk map has no files"));
+        }
+
+        Ok(DiskMap { blocks })
+    }
+}
+
+impl DiskMap {
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    // For the A solution, we walk the blocks from left to right until we find an empty block and thenwe walk from right
+    // to left until we find a used block. Then we swap the two. We continue this process until the two pointers meet.
+    pub fn optimize_a(&mut self) {
+        let mut free_index = 0;
+        let mut used_index = self.blocks.len() - 1;
+
+        while free_index < used_index {
+            match self.blocks[free_index] {
+                Block::Used(_) => {
+                    free_index += 1;
+                }
+                Block::Empty => match self.blocks[used_index] {
+                    Block::Empty => {
+                        used_index -= 1;
+                    }
+                    Block::Used(_) => {
+                        self.blocks.swap(free_index, used_index);
+                        free_index += 1;
+                        used_index -= 1;
+                    }
+                },
+            }
+        }
+    }
+
+    // So in part B we have to iterate in reverse over each block exactly once. We do this by iterating in reverse over
+    // the block id's, finding the block and it's size, then finding the first available empty space of at least that
+    // size and swap the two, but only if the first available empty space is to the left of the block.
+    pub fn optimize_b(&mut self) {
+        for id in (0..(self.get_highest_id() + 1)).rev() {
+            let (index, size) = self.get_last_block_with_id(id);
+            if let Some(free_index) = self.get_first_free_n_blocks(size) {
+                if free_index < index {
+                    for i in 0..size {
+                        self.blocks.swap(index + i, free_index + i);
+                    }
+                }
+            }
+        }
+    }
+
+    fn get_last_block_with_id(&self, id: usize) -> (usize, usize) {
+        let mut block_end = self.blocks.len();
+        let mut count = 0;
+
+        while block_end > 0 {
+            block_end -= 1;
+
+            match self.blocks[block_end] {
+                Block::Used(block_id) if block_id == id => {
+                    count += 1;
+                }
+                Block::Used(_) | Block::Empty if count == 0 => continue,
+                _ => break,
+            }
+        }
+
+        (block_end + 1, count)
+    }
+
+    fn get_first_free_n_blocks(&self, n: usize) -> Option<usize> {
+        let mut block_index = 0;
+        let mut count = 0;
+        while block_index < self.blocks.len() {
+            match self.blocks[block_index] {
+                Block::Used(_) => {
+                    block_index += 1;
+                    count = 0;
+                }
+                Block::Empty => {
+                    block_index += 1;
+                    count += 1;
+                }
+            }
+
+            // index now points to the end of the count free blocks so don't forget to subtract count. Because if you
+            // forget you'll spend an hour debugging.
+            if count == n {
+                return Some(block_index - count);
+            }
+        }
+        None
+    }
+
+    fn get_highest_id(&self) -> usize {
+        self.blocks
+            .iter()
+            .rev()
+            .find_map(|block| {
+                if let Block::Used(block_id) = block {
+                    Some(*block_id)
+                } else {
+                    None
+                }
+            })
+            .unwrap()
+    }
+
+    pub fn checksum(&self) -> usize {
+        self.blocks
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (index, block)| match block {
+                Block::Empty => acc,
+                Block::Used(id) => acc + index * id,
+            })
+    }
+}
+
+// As the AoC progresses I expected to have more and more unwraps creeping in, but now there's one less.
+pub fn parse(input: &str) -> Result<DiskMap, ParseError> {
+    input.parse()
+}
+
+pub fn part_a(disk_map: &DiskMap) -> usize {
+    let mut disk_map = disk_map.clone();
+    disk_map.optimize_a();
+    disk_map.checksum()
+}
+
+pub fn part_b(disk_map: &DiskMap) -> usize {
+    let mut disk_map = disk_map.clone();
+    disk_map.optimize_b();
+    disk_map.checksum()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = DiskMap;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(disk_map: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(disk_map))
+    }
+
+    fn part_b(disk_map: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_b(disk_map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day09-1.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day09>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_a, "1928");
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day09>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_b, "2858");
+    }
+}
@@ -0,0 +1,128 @@
+use grid::{Coordinate, Grid};
+use harness::{ParseError, Solution};
+use itertools::Itertools;
+use std::{error::Error, fmt::Display, str::FromStr};
+
+const TRAILHEAD: u8 = b'0';
+// A little lookup that has the convenience that the next number is at the index of the current number.
+const ELEVATIONS: [u8; 9] = *b"123456789";
+
+#[derive(Debug, Clone)]
+pub struct TopographicMap {
+    grid: Grid<u8>,
+}
+
+impl TopographicMap {
+    pub fn get_possible_trailheads(&self) -> Vec<Coordinate> {
+        self.grid.positions(|&c| c == TRAILHEAD).collect()
+    }
+
+    fn get_possible_next(&self, coordinate: Coordinate) -> Vec<Coordinate> {
+        let elevation = self.grid[coordinate];
+        let next_elevation = ELEVATIONS[(elevation - b'0') as usize];
+
+        self.grid
+            .neighbours4(coordinate)
+            .filter(|&neighbour| self.grid[neighbour] == next_elevation)
+            .collect()
+    }
+
+    // We simply find all possible next steps eight times in a row. Anything remaining is a reachable 9.
+    pub fn walk_trailhead(&self, trailhead: Coordinate) -> Vec<Coordinate> {
+        let mut trails = vec![trailhead];
+
+        for _ in 0..9 {
+            trails = trails
+                .into_iter()
+                .flat_map(|coordinate| self.get_possible_next(coordinate))
+                .collect();
+        }
+
+        trails
+    }
+}
+
+// Three times a charm, the padded grid is the best thing since sliced bread. So good it got its own crate. The map only
+// has heights and the odd impassable tile (.) in some of the examples, anything else we can't walk on.
+impl FromStr for TopographicMap {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        for (index, line) in input.lines().enumerate() {
+            if let Some((col, c)) = line
+                .char_indices()
+                .find(|&(_, c)| !c.is_ascii_digit() && c != '.')
+            {
+                let part = &line[col..col + c.len_utf8()];
+                let error = ParseError::in_line(line, part, "expected a height (0-9) or .");
+                return Err(error.on_line(index + 1));
+            }
+        }
+
+        Ok(Self {
+            grid: Grid::parse(input, 1, b'.'),
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Result<TopographicMap, ParseError> {
+    input.parse()
+}
+
+// Part A is more work than part B! We need to find the uniques of all the trailheads.
+pub fn part_a(map: &TopographicMap) -> usize {
+    map.get_possible_trailheads()
+        .iter()
+        .map(|&trailhead| {
+            map.walk_trailhead(trailhead)
+                .iter()
+                .unique()
+                .collect::<Vec<&Coordinate>>()
+                .len()
+        })
+        .sum()
+}
+
+pub fn part_b(map: &TopographicMap) -> usize {
+    map.get_possible_trailheads()
+        .iter()
+        .map(|&trailhead| map.walk_trailhead(trailhead).len())
+        .sum()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = TopographicMap;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part_a(map: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_a(map))
+    }
+
+    fn part_b(map: &Self::Input) -> Result<impl Display, Box<dyn Error>> {
+        Ok(part_b(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/examples/day10-1.txt");
+
+    #[test]
+    fn part_a_example() {
+        let answers = harness::solve::<Day10>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_a, "36");
+    }
+
+    #[test]
+    fn part_b_example() {
+        let answers = harness::solve::<Day10>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_b, "81");
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    fmt::{Display, Formatter},
+    ops::{Index, IndexMut},
+    sync::Arc,
+};
+
+// Three times a charm became a crate. This is the padded grid from day04, day06 and day10: the puzzle is surrounded by a
+// border of filler cells, so we can look a few cells in any direction without doing bounds checks. Coordinates always
+// include the padding, so the top left cell of the puzzle itself is at (padding, padding).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coordinate {
+    pub row: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+// The cells are shared between clones until one of them writes to the grid, only then the cells are copied. That makes
+// cloning a grid as cheap as cloning a pointer.
+#[derive(Debug, Clone)]
+pub struct Grid<T> {
+    cells: Arc<Vec<T>>,
+    row_size: usize,
+    col_size: usize,
+    padding: usize,
+}
+
+impl Coordinate {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    // Take a single step in the given direction. There is no bounds check here, that is what the padding is for.
+    pub fn step(self, direction: Direction) -> Self {
+        self.steps(direction, 1)
+    }
+
+    pub fn steps(self, direction: Direction, n: usize) -> Self {
+        let (row_delta, col_delta) = direction.delta();
+        Self {
+            row: self.row.wrapping_add_signed(row_delta * n as isize),
+            col: self.col.wrapping_add_signed(col_delta * n as isize),
+        }
+    }
+}
+
+impl Direction {
+    // Clockwise, starting at the top.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    // Clockwise, starting at the top, including the diagonals.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    // The direction vector as (row, col). Rows grow downwards, just like the lines in the input.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    // Rotate 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    // Rotate 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    // Rotate clockwise in steps of 45 degrees, which is just moving through the ALL list.
+    fn rotate(self, eighths: usize) -> Self {
+        let index = Direction::ALL.iter().position(|&d| d == self).unwrap();
+        Direction::ALL[(index + eighths) % 8]
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Build a grid from rows of cells and surround it with `padding` cells of `fill` on every side. Rows that are
+    // shorter than the longest row are filled up as well, so we always end up with a rectangle.
+    pub fn new(rows: Vec<Vec<T>>, padding: usize, fill: T) -> Self {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let col_size = width + 2 * padding;
+        let row_size = rows.len() + 2 * padding;
+
+        // All cells live in one flat vector, row after row, so cell (row, col) is at index row * col_size + col.
+        let mut cells = Vec::with_capacity(row_size * col_size);
+        cells.resize(padding * col_size, fill.clone());
+        for row in rows {
+            let missing = width - row.len();
+            cells.extend(std::iter::repeat_n(fill.clone(), padding));
+            cells.extend(row);
+            cells.extend(std::iter::repeat_n(fill.clone(), missing + padding));
+        }
+        cells.resize(row_size * col_size, fill);
+
+        Self {
+            cells: Arc::new(cells),
+            row_size,
+            col_size,
+            padding,
+        }
+    }
+}
+
+impl Grid<u8> {
+    // The usual way to get a grid: one row per line of puzzle input, one cell per byte. All puzzle inputs are plain
+    // ASCII, so a byte per cell is all we need (and four times smaller than a char).
+    pub fn parse(input: &str, padding: usize, fill: u8) -> Self {
+        Self::new(
+            input.lines().map(|line| line.as_bytes().to_vec()).collect(),
+            padding,
+            fill,
+        )
+    }
+}
+
+impl<T> Grid<T> {
+    // The number of rows, including the padding.
+    pub fn row_size(&self) -> usize {
+        self.row_size
+    }
+
+    // The number of columns, including the padding.
+    pub fn col_size(&self) -> usize {
+        self.col_size
+    }
+
+    pub fn padding(&self) -> usize {
+        self.padding
+    }
+
+    pub fn contains(&self, coordinate: Coordinate) -> bool {
+        coordinate.row < self.row_size && coordinate.col < self.col_size
+    }
+
+    pub fn get(&self, coordinate: Coordinate) -> Option<&T> {
+        if self.contains(coordinate) {
+            Some(&self.cells[self.index_of(coordinate)])
+        } else {
+            None
+        }
+    }
+
+    // A single row, padding included.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.col_size..(row + 1) * self.col_size]
+    }
+
+    fn index_of(&self, coordinate: Coordinate) -> usize {
+        coordinate.row * self.col_size + coordinate.col
+    }
+
+    // A view on this grid with some cells changed, without touching (or copying) the grid itself.
+    pub fn overlay(&self) -> Overlay<'_, T> {
+        Overlay {
+            grid: self,
+            changes: Vec::new(),
+        }
+    }
+
+    // All coordinates of the puzzle itself, so without the padding, row by row.
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> {
+        let padding = self.padding;
+        let (row_end, col_end) = (self.row_size - padding, self.col_size - padding);
+        (padding..row_end)
+            .flat_map(move |row| (padding..col_end).map(move |col| Coordinate { row, col }))
+    }
+
+    // The up to four horizontal and vertical neighbours of a coordinate.
+    pub fn neighbours4(&self, coordinate: Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        self.neighbours(coordinate, &Direction::CARDINAL)
+    }
+
+    // The up to eight neighbours of a coordinate, including the diagonals.
+    pub fn neighbours8(&self, coordinate: Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        self.neighbours(coordinate, &Direction::ALL)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        coordinate: Coordinate,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Coordinate> + 'a {
+        directions
+            .iter()
+            .map(move |&direction| coordinate.step(direction))
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    // The first coordinate (without padding) for which the predicate holds.
+    pub fn position<P>(&self, predicate: P) -> Option<Coordinate>
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.coordinates()
+            .find(|&coordinate| predicate(&self[coordinate]))
+    }
+
+    // All coordinates (without padding) for which the predicate holds.
+    pub fn positions<'a, P>(&'a self, predicate: P) -> impl Iterator<Item = Coordinate> + 'a
+    where
+        P: Fn(&T) -> bool + 'a,
+    {
+        self.coordinates()
+            .filter(move |&coordinate| predicate(&self[coordinate]))
+    }
+
+    pub fn find(&self, value: &T) -> Option<Coordinate>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coordinate: Coordinate) -> &T {
+        &self.cells[self.index_of(coordinate)]
+    }
+}
+
+// Writing is where the copy-on-write happens: if other clones still share the cells, we get our own copy first.
+impl<T: Clone> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, coordinate: Coordinate) -> &mut T {
+        let index = self.index_of(coordinate);
+        &mut Arc::make_mut(&mut self.cells)[index]
+    }
+}
+
+// The whole grid, padding included, one line per row.
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for row in 0..self.row_size {
+            for &cell in self.row(row) {
+                write!(f, "{}", cell.into())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// An overlay is a grid with a few cells changed. The changes are kept next to the grid instead of in it, so trying out
+// a change (like putting an obstacle somewhere) costs nothing more than the change itself. We expect only a handful of
+// changes, so a plain list is faster than any map.
+#[derive(Debug, Clone)]
+pub struct Overlay<'a, T> {
+    grid: &'a Grid<T>,
+    changes: Vec<(Coordinate, T)>,
+}
+
+impl<T> Overlay<'_, T> {
+    pub fn set(&mut self, coordinate: Coordinate, value: T) {
+        match self.changes.iter_mut().find(|(c, _)| *c == coordinate) {
+            Some((_, cell)) => *cell = value,
+            None => self.changes.push((coordinate, value)),
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        self.grid
+    }
+}
+
+impl<T> Index<Coordinate> for Overlay<'_, T> {
+    type Output = T;
+
+    fn index(&self, coordinate: Coordinate) -> &T {
+        self.changes
+            .iter()
+            .find(|(c, _)| *c == coordinate)
+            .map(|(_, cell)| cell)
+            .unwrap_or(&self.grid[coordinate])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pads_every_side_and_ragged_rows() {
+        let grid = Grid::parse("ab\nc\n", 1, b'.');
+
+        assert_eq!((grid.row_size(), grid.col_size()), (4, 4));
+        assert_eq!(grid.to_string(), "....\n.ab.\n.c..\n....\n");
+    }
+
+    #[test]
+    fn coordinates_skip_the_padding() {
+        let grid = Grid::parse("ab\ncd\n", 2, b'.');
+
+        assert_eq!(
+            grid.coordinates().collect::<Vec<_>>(),
+            vec![
+                Coordinate::new(2, 2),
+                Coordinate::new(2, 3),
+                Coordinate::new(3, 2),
+                Coordinate::new(3, 3),
+            ]
+        );
+        assert_eq!(grid.find(&b'c'), Some(Coordinate::new(3, 2)));
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = Grid::parse("ab\ncd\n", 1, b'.');
+
+        assert_eq!(grid.neighbours4(Coordinate::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Coordinate::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Coordinate::new(0, 0)).count(), 3);
+    }
+
+    #[test]
+    fn directions_turn_clockwise() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::UpLeft.turn_left(), Direction::DownLeft);
+        assert_eq!(Direction::DownRight.opposite(), Direction::UpLeft);
+    }
+
+    #[test]
+    fn clones_and_overlays_leave_the_original_alone() {
+        let grid = Grid::parse("ab\n", 0, b'.');
+        let a = Coordinate::new(0, 0);
+
+        let mut clone = grid.clone();
+        clone[a] = b'x';
+
+        let mut overlay = grid.overlay();
+        overlay.set(a, b'y');
+
+        assert_eq!((grid[a], clone[a], overlay[a]), (b'a', b'x', b'y'));
+        assert_eq!(overlay[Coordinate::new(0, 1)], b'b');
+    }
+}
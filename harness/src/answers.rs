@@ -0,0 +1,141 @@
+use crate::{input::inputs_dir, Answers};
+use std::{error::Error, fs, io, path::PathBuf};
+use toml::{Table, Value};
+
+// The answers we know to be right, because the puzzle site accepted them. They live next to the input they belong to,
+// in inputs/dayNN.answers.toml:
+//
+//   part_a = "1234"
+//   part_b = "5678"
+//
+// A part we haven't solved (or submitted) yet is simply left out. Numbers without quotes are fine too, most answers
+// are numbers after all.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recorded {
+    pub part_a: Option<String>,
+    pub part_b: Option<String>,
+}
+
+// How a single part compares to the recorded answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Match,
+    Mismatch { expected: String, actual: String },
+    NotRecorded,
+}
+
+impl Recorded {
+    pub fn path(day: usize) -> PathBuf {
+        inputs_dir().join(format!("day{:02}.answers.toml", day))
+    }
+
+    // Load the recorded answers of a day. Not having recorded anything yet is not an error, that's just nothing.
+    pub fn load(day: usize) -> Result<Self, Box<dyn Error>> {
+        let path = Self::path(day);
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e).into()),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+        let table: Table = text.parse()?;
+
+        let part = |name: &str| match table.get(name) {
+            None => Ok(None),
+            Some(Value::String(answer)) => Ok(Some(answer.clone())),
+            Some(Value::Integer(answer)) => Ok(Some(answer.to_string())),
+            Some(other) => Err(format!(
+                "{} should be a string or a number, not {}",
+                name, other
+            )),
+        };
+
+        Ok(Self {
+            part_a: part("part_a")?,
+            part_b: part("part_b")?,
+        })
+    }
+
+    pub fn save(&self, day: usize) -> io::Result<()> {
+        let mut table = Table::new();
+        if let Some(answer) = &self.part_a {
+            table.insert("part_a".to_string(), Value::String(answer.clone()));
+        }
+        if let Some(answer) = &self.part_b {
+            table.insert("part_b".to_string(), Value::String(answer.clone()));
+        }
+
+        fs::write(Self::path(day), table.to_string())
+    }
+
+    pub fn check(&self, answers: &Answers) -> (Check, Check) {
+        (
+            check(self.part_a.as_deref(), &answers.part_a),
+            check(self.part_b.as_deref(), &answers.part_b),
+        )
+    }
+}
+
+impl From<Answers> for Recorded {
+    fn from(answers: Answers) -> Self {
+        Self {
+            part_a: Some(answers.part_a),
+            part_b: Some(answers.part_b),
+        }
+    }
+}
+
+fn check(expected: Option<&str>, actual: &str) -> Check {
+    match expected {
+        None => Check::NotRecorded,
+        Some(expected) if expected == actual => Check::Match,
+        Some(expected) => Check::Mismatch {
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_strings_numbers_and_missing_parts() {
+        let recorded = Recorded::parse("part_a = \"abc\"\n").unwrap();
+        assert_eq!(recorded.part_a.as_deref(), Some("abc"));
+        assert_eq!(recorded.part_b, None);
+
+        let recorded = Recorded::parse("part_a = 11\npart_b = \"31\"\n").unwrap();
+        assert_eq!(recorded.part_a.as_deref(), Some("11"));
+        assert_eq!(recorded.part_b.as_deref(), Some("31"));
+
+        assert!(Recorded::parse("part_a = 1.5\n").is_err());
+        assert!(Recorded::parse("part_a = \n").is_err());
+    }
+
+    #[test]
+    fn check_compares_each_part() {
+        let recorded = Recorded {
+            part_a: Some("11".to_string()),
+            part_b: None,
+        };
+        let answers = Answers {
+            part_a: "12".to_string(),
+            part_b: "31".to_string(),
+        };
+
+        assert_eq!(
+            recorded.check(&answers),
+            (
+                Check::Mismatch {
+                    expected: "11".to_string(),
+                    actual: "12".to_string()
+                },
+                Check::NotRecorded
+            )
+        );
+    }
+}
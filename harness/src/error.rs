@@ -0,0 +1,106 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+// A parse error that tells you exactly where things went wrong in the input: the (1-based) line and column and the
+// text that couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    // Most of the parsing is done line by line and a line doesn't know where it is in the input. So errors start out on
+    // line 1 and whoever splits the input into lines moves them to the right line with `on_line`.
+    pub fn new(column: usize, text: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            line: 1,
+            column,
+            text: text.into(),
+            reason: reason.into(),
+        }
+    }
+
+    // Create an error for a part of a line. Because the part is a slice of the line (that's what split and friends
+    // give us) we can find its column by looking at where it lives in memory, no need to keep track of offsets while
+    // parsing.
+    pub fn in_line(line: &str, part: &str, reason: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&offset| offset <= line.len())
+            .expect("part should be a slice of the line");
+
+        Self::new(line[..offset].chars().count() + 1, part, reason)
+    }
+
+    // An error about something missing at the end of a line.
+    pub fn at_end(line: &str, reason: impl Into<String>) -> Self {
+        Self::in_line(line, &line[line.len()..], reason)
+    }
+
+    pub fn on_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+}
+
+// Numbers are by far the most common thing we parse, so here's a shortcut for parsing a part of a line as a number.
+pub fn parse_number<T: FromStr>(line: &str, part: &str) -> Result<T, ParseError> {
+    part.trim()
+        .parse()
+        .map_err(|_| ParseError::in_line(line, part, "expected a number"))
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.reason
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " (found {:?})", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_line_finds_the_column_of_a_part() {
+        let line = "47|5x";
+        let (_, right) = line.split_once('|').unwrap();
+
+        let error = ParseError::in_line(line, right, "expected a number").on_line(3);
+
+        assert_eq!((error.line, error.column), (3, 4));
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 4: expected a number (found \"5x\")"
+        );
+    }
+
+    #[test]
+    fn at_end_points_past_the_last_character() {
+        let error = ParseError::at_end("3   4", "expected a third number");
+        assert_eq!((error.line, error.column), (1, 6));
+    }
+
+    #[test]
+    fn parse_number_reports_the_offending_part() {
+        let line = "190: 10 1a9";
+        let part = line.split_whitespace().last().unwrap();
+
+        assert_eq!(parse_number::<usize>(line, "10"), Ok(10));
+        assert_eq!(parse_number::<usize>(line, part).unwrap_err().column, 9);
+    }
+}
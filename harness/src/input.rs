@@ -0,0 +1,139 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+// Where to read the puzzle input from. By default that's the input of the day in the inputs directory, but we can also
+// pick one of the published examples, any other file or whatever is piped into stdin.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Source {
+    #[default]
+    Default,
+    Example(usize),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    // Take the input options out of the command line arguments and leave everything else (the days to run, day06's
+    // visual mode) for the caller. The options are:
+    //
+    //   --input <path>  read the input from a file
+    //   --example <n>   read the n-th published example of the day
+    //   - (or --input -) read the input from stdin
+    pub fn from_args(args: &mut Vec<String>) -> Result<Self, String> {
+        let mut source = Source::Default;
+        let mut remaining = Vec::new();
+
+        let mut args_iter = std::mem::take(args).into_iter();
+        while let Some(arg) = args_iter.next() {
+            match arg.as_str() {
+                "-" => source = Source::Stdin,
+                "--input" => {
+                    source = match args_iter.next().as_deref() {
+                        Some("-") => Source::Stdin,
+                        Some(path) => Source::Path(path.into()),
+                        None => return Err("--input needs a path".to_string()),
+                    }
+                }
+                "--example" => {
+                    source = args_iter
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .map(Source::Example)
+                        .ok_or("--example needs a number")?
+                }
+                _ => remaining.push(arg),
+            }
+        }
+
+        *args = remaining;
+        Ok(source)
+    }
+
+    // The file to read for a day, there is none when reading from stdin.
+    pub fn path(&self, day: usize) -> Option<PathBuf> {
+        match self {
+            Source::Default => Some(inputs_dir().join(format!("day{:02}.txt", day))),
+            Source::Example(n) => Some(
+                inputs_dir()
+                    .join("examples")
+                    .join(format!("day{:02}-{}.txt", day, n)),
+            ),
+            Source::Path(path) => Some(path.clone()),
+            Source::Stdin => None,
+        }
+    }
+
+    // Read the whole input for a day. A missing file is the most common mistake, so the error includes the path.
+    pub fn read(&self, day: usize) -> io::Result<String> {
+        match self.path(day) {
+            Some(path) => fs::read_to_string(&path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e))),
+            None => {
+                let mut input = String::new();
+                io::stdin().lock().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    // Open the input of a day for reading line by line, for inputs that are too big to read in one go.
+    pub fn open(&self, day: usize) -> io::Result<Box<dyn BufRead>> {
+        match self.path(day) {
+            Some(path) => File::open(&path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e))),
+            None => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+}
+
+// The inputs directory is in the root of the workspace, right next to this crate. We take the location from cargo, so
+// it doesn't matter from which directory a day is run.
+pub fn inputs_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the harness crate lives in the workspace")
+        .join("inputs")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn from_args_leaves_other_arguments_alone() {
+        let mut remaining = args(&["run", "3..7", "--example", "2"]);
+
+        assert_eq!(Source::from_args(&mut remaining), Ok(Source::Example(2)));
+        assert_eq!(remaining, args(&["run", "3..7"]));
+    }
+
+    #[test]
+    fn from_args_understands_all_sources() {
+        let source = |list: &[&str]| Source::from_args(&mut args(list));
+
+        assert_eq!(source(&["visual"]), Ok(Source::Default));
+        assert_eq!(
+            source(&["--input", "x.txt"]),
+            Ok(Source::Path("x.txt".into()))
+        );
+        assert_eq!(source(&["--input", "-"]), Ok(Source::Stdin));
+        assert_eq!(source(&["-"]), Ok(Source::Stdin));
+        assert!(source(&["--example", "one"]).is_err());
+        assert!(source(&["--input"]).is_err());
+    }
+
+    #[test]
+    fn examples_are_in_the_inputs_directory() {
+        let path = Source::Example(2).path(3).unwrap();
+        assert_eq!(path, inputs_dir().join("examples").join("day03-2.txt"));
+        assert!(path.exists());
+    }
+}
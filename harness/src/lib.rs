@@ -0,0 +1,108 @@
+pub mod answers;
+mod error;
+pub mod input;
+
+pub use answers::{Check, Recorded};
+pub use error::{parse_number, ParseError};
+pub use input::Source;
+
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+// Every day follows the same recipe: turn the puzzle input into some typed structure and then compute the two answers
+// from that structure. The trait captures exactly that, so the runner (and anything else that wants to time, test or
+// benchmark a day) doesn't have to know anything about the day itself.
+//
+// The parts only get a shared reference to the input, so a day that needs to mutate its input (moving guards around,
+// compacting disks) clones it first. That keeps part A and part B independent of each other and of the order in which
+// they're called.
+//
+// Most parts can't fail once the input is parsed, but some can (rules that go round in circles, a score that doesn't
+// fit). Those return an error instead of an answer, so an error never ends up in a table or in the recorded answers as
+// if it were one.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_a(input: &Self::Input) -> Result<impl Display, Box<dyn Error>>;
+    fn part_b(input: &Self::Input) -> Result<impl Display, Box<dyn Error>>;
+}
+
+// The answers of a single day. Answers can be anything that can be displayed, so we store them as strings so all days
+// can be treated the same.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_a: String,
+    pub part_b: String,
+}
+
+// How long each stage of a day took, wall clock time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_a: Duration,
+    pub part_b: Duration,
+}
+
+// Run all stages of a day on the given input.
+pub fn solve<S: Solution>(input: &str) -> Result<Answers, Box<dyn Error>> {
+    solve_timed::<S>(input).map(|(answers, _)| answers)
+}
+
+// The same, but also keep track of the time spent in every stage. Turning the answer into a string is part of the
+// stage, for some days that's where the lazy work happens.
+pub fn solve_timed<S: Solution>(input: &str) -> Result<(Answers, Timings), Box<dyn Error>> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let part_a = S::part_a(&input)?.to_string();
+    let part_a_time = start.elapsed();
+
+    let start = Instant::now();
+    let part_b = S::part_b(&input)?.to_string();
+    let part_b_time = start.elapsed();
+
+    let timings = Timings {
+        parse,
+        part_a: part_a_time,
+        part_b: part_b_time,
+    };
+    Ok((Answers { part_a, part_b }, timings))
+}
+
+// Everything a day's own binary has to do: find out where to read the input from, solve the day and print the answers.
+pub fn main<S: Solution>(day: usize) -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let result = Source::from_args(&mut args)
+        .map_err(Into::into)
+        .and_then(|source| run::<S>(day, &source));
+    exit(result)
+}
+
+// The end of every binary. An error returned from `main` is printed with Debug, which for a parse error is the whole
+// struct instead of the message that says where the input went wrong. So we print the error ourselves.
+pub fn exit(result: Result<(), Box<dyn Error>>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn run<S: Solution>(day: usize, source: &Source) -> Result<(), Box<dyn Error>> {
+    let answers = solve::<S>(&source.read(day)?)?;
+
+    println!("Part A: {}", answers.part_a);
+    println!("Part B: {}", answers.part_b);
+
+    Ok(())
+}